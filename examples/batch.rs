@@ -28,7 +28,7 @@ async fn main() -> anyhow::Result<()> {
                 exec.affected_row_count, exec.last_insert_rowid
             ),
             StatementOutcome::Query(query) => {
-                println!("query: {} row(s)", query.rows.len());
+                println!("query: {} row(s)", query.row_count());
             }
             StatementOutcome::SqlError {
                 request_index,
@@ -37,6 +37,7 @@ async fn main() -> anyhow::Result<()> {
             } => {
                 eprintln!("sql error at index {request_index}: {message}");
             }
+            other => println!("other outcome: {other:?}"),
         }
     }
 
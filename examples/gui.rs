@@ -7,8 +7,6 @@ use bunnydb_http::{
     BunnyDbClient, ExecResult, Params, QueryResult, Statement, StatementOutcome, Value,
 };
 use eframe::egui::{self, Color32, RichText, TextEdit};
-use serde::Deserialize;
-use serde_json::Value as JsonValue;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum AuthMode {
@@ -37,14 +35,6 @@ enum LastResult {
     Batch(Vec<StatementOutcome>),
 }
 
-#[derive(Debug, Deserialize)]
-struct BatchInputStatement {
-    kind: String,
-    sql: String,
-    #[serde(default)]
-    params: Option<JsonValue>,
-}
-
 struct BunnyGuiApp {
     auth_mode: AuthMode,
     mode: OperationMode,
@@ -321,7 +311,7 @@ impl BunnyGuiApp {
                 match message {
                     UiResponse::Query(result) => match result {
                         Ok(value) => {
-                            self.status = format!("Query OK ({} rows)", value.rows.len());
+                            self.status = format!("Query OK ({} rows)", value.row_count());
                             self.last_result = Some(LastResult::Query(value));
                             self.last_error = None;
                         }
@@ -452,104 +442,15 @@ fn validate_connection_fields(pipeline_url: &str, auth: &str) -> Result<(), Stri
 }
 
 fn parse_batch_json(input: &str) -> Result<Vec<Statement>, String> {
-    let trimmed = input.trim();
-    if trimmed.is_empty() {
-        return Err("batch JSON cannot be empty".to_owned());
-    }
-
-    let parsed: Vec<BatchInputStatement> =
-        serde_json::from_str(trimmed).map_err(|err| format!("invalid batch JSON: {err}"))?;
-
-    let mut out = Vec::with_capacity(parsed.len());
-    for (index, entry) in parsed.into_iter().enumerate() {
-        if entry.sql.trim().is_empty() {
-            return Err(format!("batch[{index}] has empty SQL"));
-        }
-
-        let params = match entry.params {
-            Some(value) => parse_params_value(value)?,
-            None => Params::default(),
-        };
-
-        if entry.kind.eq_ignore_ascii_case("query") {
-            out.push(Statement::query(entry.sql, params));
-        } else if entry.kind.eq_ignore_ascii_case("execute") {
-            out.push(Statement::execute(entry.sql, params));
-        } else {
-            return Err(format!(
-                "batch[{index}] invalid kind '{}': expected 'query' or 'execute'",
-                entry.kind
-            ));
-        }
-    }
-
-    Ok(out)
+    Statement::batch_from_json(input).map_err(|err| err.to_string())
 }
 
 fn parse_params_json(input: &str) -> Result<Params, String> {
-    let trimmed = input.trim();
-    if trimmed.is_empty() {
-        return Ok(Params::default());
-    }
-    let parsed = serde_json::from_str::<JsonValue>(trimmed)
-        .map_err(|err| format!("invalid params JSON: {err}"))?;
-    parse_params_value(parsed)
-}
-
-fn parse_params_value(value: JsonValue) -> Result<Params, String> {
-    match value {
-        JsonValue::Array(items) => {
-            let values = items
-                .into_iter()
-                .map(parse_value_json)
-                .collect::<Result<Vec<_>, _>>()?;
-            Ok(Params::positional(values))
-        }
-        JsonValue::Object(map) => {
-            let values = map
-                .into_iter()
-                .map(|(key, value)| parse_value_json(value).map(|parsed| (key, parsed)))
-                .collect::<Result<Vec<_>, _>>()?;
-            Ok(Params::named(values))
-        }
-        JsonValue::Null => Ok(Params::default()),
-        _ => Err("params JSON must be either array, object, or null".to_owned()),
-    }
-}
-
-fn parse_value_json(value: JsonValue) -> Result<Value, String> {
-    match value {
-        JsonValue::Null => Ok(Value::Null),
-        JsonValue::Bool(flag) => Ok(Value::integer(i64::from(flag))),
-        JsonValue::Number(number) => {
-            if let Some(i) = number.as_i64() {
-                return Ok(Value::integer(i));
-            }
-            if let Some(f) = number.as_f64() {
-                if !f.is_finite() {
-                    return Err("non-finite float is not supported".to_owned());
-                }
-                return Ok(Value::float(f));
-            }
-            Err(format!("unsupported number '{number}'"))
-        }
-        JsonValue::String(text) => Ok(Value::text(text)),
-        JsonValue::Array(_) => {
-            Err("nested arrays are not supported in parameter values".to_owned())
-        }
-        JsonValue::Object(mut map) => {
-            if map.len() == 1 {
-                if let Some(JsonValue::String(blob)) = map.remove("blob_base64") {
-                    return Ok(Value::blob_base64(blob));
-                }
-            }
-            Err("object parameter values must be {\"blob_base64\": \"...\"}".to_owned())
-        }
-    }
+    Params::from_json(input).map_err(|err| err.to_string())
 }
 
 fn render_query_result(ui: &mut egui::Ui, result: &QueryResult) {
-    ui.label(format!("Rows: {}", result.rows.len()));
+    ui.label(format!("Rows: {}", result.row_count()));
     ui.label(format!("Replication index: {:?}", result.replication_index));
     ui.label(format!("Rows read: {:?}", result.rows_read));
     ui.label(format!("Rows written: {:?}", result.rows_written));
@@ -607,7 +508,7 @@ fn render_batch_result(ui: &mut egui::Ui, outcomes: &[StatementOutcome]) {
                     StatementOutcome::Query(query) => {
                         ui.label(format!(
                             "[{index}] QUERY ok: rows={}, duration_ms={:?}",
-                            query.rows.len(),
+                            query.row_count(),
                             query.query_duration_ms
                         ));
                     }
@@ -615,6 +516,7 @@ fn render_batch_result(ui: &mut egui::Ui, outcomes: &[StatementOutcome]) {
                         request_index,
                         message,
                         code,
+                        ..
                     } => {
                         ui.colored_label(
                             Color32::from_rgb(215, 40, 40),
@@ -624,6 +526,9 @@ fn render_batch_result(ui: &mut egui::Ui, outcomes: &[StatementOutcome]) {
                             ),
                         );
                     }
+                    other => {
+                        ui.label(format!("[{index}] {other:?}"));
+                    }
                 }
             }
         });
@@ -636,6 +541,7 @@ fn display_value(value: &Value) -> String {
         Value::Float(v) => v.to_string(),
         Value::Text(v) => v.clone(),
         Value::BlobBase64(v) => format!("<blob:{} chars>", v.len()),
+        other => format!("{other:?}"),
     }
 }
 
@@ -28,9 +28,13 @@ await init(fetch(process.env.WASM_URL ?? "https://your-zone.b-cdn.net/bunnydb_ed
 
 // Create the Rust BunnyDB handler.
 // Credentials are injected by Bunny via environment variables (Access page → Generate Token).
-const db = process.env.DB_ID
-    ? BunnyEdgeHandler.from_db_id(process.env.DB_ID, process.env.DB_TOKEN!)
-    : new BunnyEdgeHandler(process.env.DB_URL!, process.env.DB_TOKEN!);
+// Throws at cold start if neither DB_URL nor DB_ID is set, or DB_TOKEN is missing.
+const db = BunnyEdgeHandler.from_config({
+    pipeline_url: process.env.DB_URL,
+    db_id: process.env.DB_ID,
+    token: process.env.DB_TOKEN,
+    options: { timeout_ms: 3000 },
+});
 
 // ── Router ─────────────────────────────────────────────────────────────────
 
@@ -51,6 +55,22 @@ BunnySDK.net.http.serve(async (req: Request): Promise<Response> => {
         }
     }
 
+    // GET /users/search?name=Kit → parameterized lookup; the name is bound, not spliced
+    if (method === "GET" && url.pathname === "/users/search") {
+        try {
+            const json = await db.query_params_json(
+                "SELECT id, name, email FROM users WHERE name = :name",
+                JSON.stringify({ name: url.searchParams.get("name") ?? "" }),
+            );
+            return new Response(json, {
+                status: 200,
+                headers: { "Content-Type": "application/json" },
+            });
+        } catch (err) {
+            return errorResponse(500, String(err));
+        }
+    }
+
     // POST /users  body: { "name": "...", "email": "..." }
     if (method === "POST" && url.pathname === "/users") {
         try {
@@ -25,9 +25,50 @@
 //!               └── BunnyDB /v2/pipeline
 //! ```
 
-use bunnydb_http::{BunnyDbClient, Value};
+use bunnydb_http::{
+    BunnyDbClient, ClientOptions, Params, QueryResult, Statement, StatementOutcome, Value,
+};
+use serde::Deserialize;
 use wasm_bindgen::prelude::*;
 
+// ── Configuration ───────────────────────────────────────────────────────────
+
+/// Shape of the object accepted by [`BunnyEdgeHandler::from_config`].
+#[derive(Deserialize)]
+struct EdgeConfig {
+    pipeline_url: Option<String>,
+    db_id: Option<String>,
+    token: Option<String>,
+    #[serde(default)]
+    options: EdgeOptions,
+}
+
+/// Subset of [`ClientOptions`] settable from JS; omitted fields keep the
+/// crate defaults.
+#[derive(Default, Deserialize)]
+struct EdgeOptions {
+    timeout_ms: Option<u64>,
+    max_retries: Option<usize>,
+    retry_backoff_ms: Option<u64>,
+}
+
+impl EdgeOptions {
+    fn into_client_options(self) -> ClientOptions {
+        let defaults = ClientOptions::default();
+        ClientOptions {
+            timeout_ms: self.timeout_ms.unwrap_or(defaults.timeout_ms),
+            max_retries: self.max_retries.unwrap_or(defaults.max_retries),
+            retry_backoff_ms: self.retry_backoff_ms.unwrap_or(defaults.retry_backoff_ms),
+            ..defaults
+        }
+    }
+}
+
+/// Treats a blank string like a missing one.
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.trim().is_empty())
+}
+
 // ── Handler struct ──────────────────────────────────────────────────────────
 
 /// WASM-exported handle to a connected BunnyDB client.
@@ -74,6 +115,41 @@ impl BunnyEdgeHandler {
         }
     }
 
+    /// Creates a handler from a single configuration object:
+    ///
+    /// ```typescript
+    /// const handler = BunnyEdgeHandler.from_config({
+    ///     db_id: process.env.DB_ID,          // or pipeline_url
+    ///     token: process.env.DB_TOKEN,
+    ///     options: { timeout_ms: 3000, max_retries: 1 },
+    /// });
+    /// ```
+    ///
+    /// `pipeline_url` takes precedence over `db_id` when both are set.
+    /// `options` is optional and accepts `timeout_ms`, `max_retries` and
+    /// `retry_backoff_ms`. Throws when the object has the wrong shape, when
+    /// neither `pipeline_url` nor `db_id` is set, or when `token` is missing.
+    pub fn from_config(config: JsValue) -> Result<BunnyEdgeHandler, JsError> {
+        let config: EdgeConfig = serde_wasm_bindgen::from_value(config)
+            .map_err(|err| JsError::new(&format!("invalid config: {err}")))?;
+        let token =
+            non_empty(config.token).ok_or_else(|| JsError::new("config.token is required"))?;
+
+        let db = match (non_empty(config.pipeline_url), non_empty(config.db_id)) {
+            (Some(url), _) => BunnyDbClient::new_bearer(url, token),
+            (None, Some(db_id)) => BunnyDbClient::from_db_id(db_id, token),
+            (None, None) => {
+                return Err(JsError::new(
+                    "config.pipeline_url or config.db_id is required",
+                ))
+            }
+        };
+
+        Ok(BunnyEdgeHandler {
+            db: db.with_options(config.options.into_client_options()),
+        })
+    }
+
     // ── Query helpers ───────────────────────────────────────────────────────
 
     /// Runs a raw SQL SELECT and returns all rows as a JSON string.
@@ -89,23 +165,34 @@ impl BunnyEdgeHandler {
     /// ```
     pub async fn query_json(&self, sql: String) -> Result<String, String> {
         let result = self.db.query(&sql, ()).await.map_err(|e| e.to_string())?;
+        serde_json::to_string(&query_result_to_json(&result)).map_err(|e| e.to_string())
+    }
 
-        let col_names: Vec<&str> = result.cols.iter().map(|c| c.name.as_str()).collect();
-        let rows: Vec<Vec<serde_json::Value>> = result
-            .rows
-            .iter()
-            .map(|row| row.iter().map(value_to_json).collect())
-            .collect();
-
-        let payload = serde_json::json!({
-            "cols": col_names,
-            "rows": rows,
-            "rows_read": result.rows_read,
-            "rows_written": result.rows_written,
-            "query_duration_ms": result.query_duration_ms,
-        });
-
-        serde_json::to_string(&payload).map_err(|e| e.to_string())
+    /// Runs a parameterized SELECT and returns rows in the `query_json` shape.
+    ///
+    /// `params_json` is a positional array for `?` placeholders or an object
+    /// for named ones, e.g. `[1, "Kit"]` or `{ "name": "Kit" }`; values may
+    /// also be `null`, booleans or `{ "blob_base64": "..." }`. Values are
+    /// bound server-side, never spliced into the SQL text:
+    ///
+    /// ```typescript
+    /// await handler.query_params_json(
+    ///     "SELECT id, name FROM users WHERE name = :name",
+    ///     JSON.stringify({ name: url.searchParams.get("name") }),
+    /// );
+    /// ```
+    pub async fn query_params_json(
+        &self,
+        sql: String,
+        params_json: String,
+    ) -> Result<String, String> {
+        let params = Params::from_json(&params_json).map_err(|e| e.to_string())?;
+        let result = self
+            .db
+            .query(&sql, params)
+            .await
+            .map_err(|e| e.to_string())?;
+        serde_json::to_string(&query_result_to_json(&result)).map_err(|e| e.to_string())
     }
 
     /// Executes a SQL statement (INSERT / UPDATE / DELETE / DDL).
@@ -151,6 +238,95 @@ impl BunnyEdgeHandler {
 
         serde_json::to_string(&payload).map_err(|e| e.to_string())
     }
+
+    /// Runs several statements in one pipeline request.
+    ///
+    /// `batch_json` is an array of `{ kind, sql, params? }` entries, the same
+    /// shape the desktop GUI example accepts; `kind` is `"query"` or
+    /// `"execute"` and `params` a positional array or named object:
+    ///
+    /// ```typescript
+    /// await handler.batch_json(JSON.stringify([
+    ///     { kind: "execute", sql: "INSERT INTO users(name) VALUES (?)", params: ["Kit"] },
+    ///     { kind: "query", sql: "SELECT count(*) AS n FROM users" },
+    /// ]));
+    /// ```
+    ///
+    /// Returns a JSON array with one entry per statement, in order. A SQL
+    /// error in one statement does not fail the others:
+    /// ```json
+    /// [
+    ///   { "type": "exec", "affected_row_count": 1, "last_insert_rowid": 42, "rows_written": 1 },
+    ///   { "type": "query", "cols": ["n"], "rows": [[3]], "rows_read": 3, "rows_written": null, "query_duration_ms": 0.2 },
+    ///   { "type": "error", "index": 2, "message": "no such table: logs", "code": "SQLITE_ERROR" }
+    /// ]
+    /// ```
+    /// Invalid input, transport and HTTP failures reject the whole call.
+    pub async fn batch_json(&self, batch_json: String) -> Result<String, String> {
+        let statements = Statement::batch_from_json(&batch_json).map_err(|e| e.to_string())?;
+        let outcomes = self.db.batch(statements).await.map_err(|e| e.to_string())?;
+
+        let payload: Vec<serde_json::Value> = outcomes.iter().map(outcome_to_json).collect();
+        serde_json::to_string(&payload).map_err(|e| e.to_string())
+    }
+}
+
+// ── Result conversion helpers ───────────────────────────────────────────────
+
+/// Serializes a query result to the `query_json` response shape.
+fn query_result_to_json(result: &QueryResult) -> serde_json::Value {
+    let col_names: Vec<&str> = result.cols.iter().map(|c| c.name.as_str()).collect();
+    let rows: Vec<Vec<serde_json::Value>> = result
+        .rows
+        .iter()
+        .map(|row| row.iter().map(value_to_json).collect())
+        .collect();
+
+    serde_json::json!({
+        "cols": col_names,
+        "rows": rows,
+        "rows_read": result.rows_read,
+        "rows_written": result.rows_written,
+        "query_duration_ms": result.query_duration_ms,
+    })
+}
+
+/// Serializes one batch outcome; see [`BunnyEdgeHandler::batch_json`].
+fn outcome_to_json(outcome: &StatementOutcome) -> serde_json::Value {
+    match outcome {
+        StatementOutcome::Query(result) => {
+            let mut payload = query_result_to_json(result);
+            payload["type"] = "query".into();
+            payload
+        }
+        StatementOutcome::Exec(result) => serde_json::json!({
+            "type": "exec",
+            "affected_row_count": result.affected_row_count,
+            "last_insert_rowid": result.last_insert_rowid,
+            "rows_written": result.rows_written,
+        }),
+        StatementOutcome::SqlError {
+            request_index,
+            message,
+            code,
+            ..
+        } => serde_json::json!({
+            "type": "error",
+            "index": request_index,
+            "message": message,
+            "code": code,
+        }),
+        StatementOutcome::DecodeError {
+            request_index,
+            message,
+        } => serde_json::json!({
+            "type": "error",
+            "index": request_index,
+            "message": message,
+            "code": null,
+        }),
+        _ => serde_json::json!({ "type": "unknown" }),
+    }
 }
 
 // ── Value conversion helpers ────────────────────────────────────────────────
@@ -164,6 +340,7 @@ fn value_to_json(v: &Value) -> serde_json::Value {
         Value::Float(f) => serde_json::json!(f),
         Value::Text(s) => serde_json::json!(s),
         Value::BlobBase64(b) => serde_json::json!(b),
+        _ => serde_json::Value::Null,
     }
 }
 
@@ -0,0 +1,118 @@
+//! Retry backoff strategies; see [`crate::ClientOptions::backoff`].
+
+use std::time::Duration;
+
+/// Computes how long to wait before a retry.
+///
+/// Implement this for custom policies such as decorrelated jitter and set it
+/// as [`crate::ClientOptions::backoff`].
+///
+/// ```
+/// use std::{sync::Arc, time::Duration};
+/// use bunnydb_http::{BackoffStrategy, ClientOptions};
+///
+/// /// Doubles like the default, but never waits more than two seconds.
+/// struct Capped;
+///
+/// impl BackoffStrategy for Capped {
+///     fn delay(&self, attempt: usize) -> Duration {
+///         let exp = attempt.min(16) as u32;
+///         Duration::from_millis(100 << exp).min(Duration::from_secs(2))
+///     }
+/// }
+///
+/// let options = ClientOptions {
+///     max_retries: 5,
+///     backoff: Some(Arc::new(Capped)),
+///     ..ClientOptions::default()
+/// };
+/// ```
+pub trait BackoffStrategy: Send + Sync {
+    /// Delay before the retry that follows `attempt`, where `0` is the
+    /// initial attempt.
+    fn delay(&self, attempt: usize) -> Duration;
+}
+
+/// Doubles the delay after every attempt: `base`, `2 * base`, `4 * base`, …
+///
+/// The doubling stops after 16 attempts. This is the default strategy, with
+/// `base` taken from [`crate::ClientOptions::retry_backoff_ms`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Exponential {
+    /// Delay before the first retry.
+    pub base: Duration,
+}
+
+impl BackoffStrategy for Exponential {
+    fn delay(&self, attempt: usize) -> Duration {
+        let exp = attempt.min(16) as u32;
+        self.base.saturating_mul(1 << exp)
+    }
+}
+
+/// Grows the delay by `step` after every attempt: `step`, `2 * step`, …
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Linear {
+    /// Delay before the first retry, and the increment after each one.
+    pub step: Duration,
+}
+
+impl BackoffStrategy for Linear {
+    fn delay(&self, attempt: usize) -> Duration {
+        let factor = u32::try_from(attempt.saturating_add(1)).unwrap_or(u32::MAX);
+        self.step.saturating_mul(factor)
+    }
+}
+
+/// Waits the same `delay` before every retry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Constant {
+    /// Delay before each retry.
+    pub delay: Duration,
+}
+
+impl BackoffStrategy for Constant {
+    fn delay(&self, _attempt: usize) -> Duration {
+        self.delay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{BackoffStrategy, Constant, Exponential, Linear};
+
+    fn delays_ms(strategy: &dyn BackoffStrategy, attempts: usize) -> Vec<u128> {
+        (0..attempts)
+            .map(|attempt| strategy.delay(attempt).as_millis())
+            .collect()
+    }
+
+    #[test]
+    fn exponential_doubles_and_stops_growing() {
+        let strategy = Exponential {
+            base: Duration::from_millis(250),
+        };
+        assert_eq!(delays_ms(&strategy, 4), [250, 500, 1_000, 2_000]);
+        assert_eq!(strategy.delay(16), strategy.delay(100));
+        assert_eq!(strategy.delay(16), Duration::from_millis(250 << 16));
+    }
+
+    #[test]
+    fn linear_adds_one_step_per_attempt() {
+        let strategy = Linear {
+            step: Duration::from_millis(100),
+        };
+        assert_eq!(delays_ms(&strategy, 4), [100, 200, 300, 400]);
+    }
+
+    #[test]
+    fn constant_never_changes() {
+        let strategy = Constant {
+            delay: Duration::from_millis(75),
+        };
+        assert_eq!(delays_ms(&strategy, 3), [75, 75, 75]);
+        assert_eq!(strategy.delay(usize::MAX), Duration::from_millis(75));
+    }
+}
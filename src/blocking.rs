@@ -0,0 +1,80 @@
+//! Synchronous facade over [`BunnyDbClient`].
+//!
+//! Enabled with the `blocking` feature; not available on `wasm32` targets.
+//! The client owns a single-threaded tokio runtime and drives every call with
+//! `block_on`, so it **must not be used from within an async context** (doing
+//! so panics, as with `reqwest::blocking`).
+
+use std::sync::Arc;
+
+use tokio::runtime::{Builder, Runtime};
+
+use crate::{
+    BunnyDbClient, BunnyDbError, ClientOptions, ExecResult, Params, QueryResult, Result, Statement,
+    StatementOutcome,
+};
+
+/// Blocking wrapper around [`BunnyDbClient`].
+///
+/// Clones share the same runtime and connection pool.
+///
+/// # Example
+///
+/// ```no_run
+/// use bunnydb_http::{blocking::BlockingBunnyDbClient, BunnyDbClient};
+///
+/// let db = BlockingBunnyDbClient::new(BunnyDbClient::from_db_id("my-db-id", "my-token"))
+///     .expect("runtime must start");
+/// let result = db.query("SELECT 1", ()).expect("query must succeed");
+/// println!("rows={}", result.row_count());
+/// ```
+#[derive(Clone, Debug)]
+pub struct BlockingBunnyDbClient {
+    inner: BunnyDbClient,
+    runtime: Arc<Runtime>,
+}
+
+impl BlockingBunnyDbClient {
+    /// Wraps an async client, starting the runtime used for all calls.
+    ///
+    /// Fails with [`BunnyDbError::Config`] if the runtime cannot be started.
+    pub fn new(client: BunnyDbClient) -> Result<Self> {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|err| BunnyDbError::Config(format!("failed to start runtime: {err}")))?;
+        Ok(Self {
+            inner: client,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// Applies client options such as timeout and retry behavior.
+    pub fn with_options(mut self, opts: ClientOptions) -> Self {
+        self.inner = self.inner.with_options(opts);
+        self
+    }
+
+    /// Returns the wrapped async client.
+    pub fn inner(&self) -> &BunnyDbClient {
+        &self.inner
+    }
+
+    /// Blocking version of [`BunnyDbClient::query`].
+    pub fn query<P: Into<Params>>(&self, sql: &str, params: P) -> Result<QueryResult> {
+        self.runtime.block_on(self.inner.query(sql, params))
+    }
+
+    /// Blocking version of [`BunnyDbClient::execute`].
+    pub fn execute<P: Into<Params>>(&self, sql: &str, params: P) -> Result<ExecResult> {
+        self.runtime.block_on(self.inner.execute(sql, params))
+    }
+
+    /// Blocking version of [`BunnyDbClient::batch`].
+    pub fn batch<I>(&self, statements: I) -> Result<Vec<StatementOutcome>>
+    where
+        I: IntoIterator<Item = Statement>,
+    {
+        self.runtime.block_on(self.inner.batch(statements))
+    }
+}
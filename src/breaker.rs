@@ -0,0 +1,142 @@
+//! Circuit breaker shared by all clones of a client.
+//!
+//! States: closed (counting consecutive failures), open (rejecting requests
+//! until the cooldown elapses) and half-open (one probe request in flight).
+
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::{clock::Stopwatch, BunnyDbError, Result};
+
+#[derive(Debug)]
+enum State {
+    Closed {
+        failures: usize,
+    },
+    Open {
+        since: Stopwatch,
+    },
+    /// A probe was admitted at `since`. If it never reports back (e.g. the
+    /// request future was dropped), another probe is admitted after the
+    /// next cooldown.
+    HalfOpen {
+        since: Stopwatch,
+    },
+}
+
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    state: Mutex<State>,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self {
+            state: Mutex::new(State::Closed { failures: 0 }),
+        }
+    }
+}
+
+impl CircuitBreaker {
+    /// Admits a request, or fails with [`BunnyDbError::CircuitOpen`] while
+    /// the breaker is open or a half-open probe is already in flight.
+    pub(crate) fn acquire(&self, cooldown: Duration) -> Result<()> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        match &*state {
+            State::Closed { .. } => Ok(()),
+            State::Open { since } | State::HalfOpen { since } => {
+                let elapsed = since.elapsed();
+                if elapsed >= cooldown {
+                    *state = State::HalfOpen {
+                        since: Stopwatch::start(),
+                    };
+                    Ok(())
+                } else {
+                    Err(BunnyDbError::CircuitOpen {
+                        retry_after_ms: duration_ms(cooldown - elapsed),
+                    })
+                }
+            }
+        }
+    }
+
+    pub(crate) fn record_success(&self) {
+        *self.state.lock().unwrap_or_else(PoisonError::into_inner) = State::Closed { failures: 0 };
+    }
+
+    /// Counts a failed request; opens the breaker once `threshold`
+    /// consecutive failures are reached or when a half-open probe fails.
+    pub(crate) fn record_failure(&self, threshold: usize) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let failures = match &*state {
+            State::Closed { failures } => failures + 1,
+            State::Open { .. } | State::HalfOpen { .. } => threshold,
+        };
+        *state = if failures >= threshold {
+            State::Open {
+                since: Stopwatch::start(),
+            }
+        } else {
+            State::Closed { failures }
+        };
+    }
+}
+
+fn duration_ms(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis())
+        .unwrap_or(u64::MAX)
+        .max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::CircuitBreaker;
+    use crate::BunnyDbError;
+
+    #[test]
+    fn opens_after_threshold_and_half_opens_after_cooldown() {
+        let breaker = CircuitBreaker::default();
+        let cooldown = Duration::from_millis(20);
+
+        breaker.record_failure(2);
+        assert!(breaker.acquire(cooldown).is_ok());
+        breaker.record_failure(2);
+        assert!(matches!(
+            breaker.acquire(cooldown),
+            Err(BunnyDbError::CircuitOpen { .. })
+        ));
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(breaker.acquire(cooldown).is_ok(), "probe must be admitted");
+        assert!(
+            breaker.acquire(cooldown).is_err(),
+            "only one probe may be in flight"
+        );
+
+        breaker.record_success();
+        assert!(breaker.acquire(cooldown).is_ok());
+    }
+
+    #[test]
+    fn failed_probe_reopens_breaker() {
+        let breaker = CircuitBreaker::default();
+        let cooldown = Duration::from_millis(10);
+
+        breaker.record_failure(1);
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(breaker.acquire(cooldown).is_ok());
+        breaker.record_failure(1);
+        assert!(breaker.acquire(cooldown).is_err());
+    }
+
+    #[test]
+    fn success_resets_failure_count() {
+        let breaker = CircuitBreaker::default();
+        breaker.record_failure(2);
+        breaker.record_success();
+        breaker.record_failure(2);
+        assert!(breaker.acquire(Duration::from_secs(60)).is_ok());
+    }
+}
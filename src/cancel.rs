@@ -0,0 +1,61 @@
+//! Cancellable variants of the single-statement helpers (native only).
+
+use std::future::Future;
+
+use tokio_util::sync::CancellationToken;
+
+use crate::{BunnyDbClient, BunnyDbError, ExecResult, Params, QueryResult, Result};
+
+impl BunnyDbClient {
+    /// Like [`BunnyDbClient::query`], but aborts the in-flight request and
+    /// returns [`BunnyDbError::Cancelled`] once `cancel` is triggered.
+    ///
+    /// **Not available on `wasm32` targets.**
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run(db: bunnydb_http::BunnyDbClient) -> bunnydb_http::Result<()> {
+    /// use bunnydb_http::CancellationToken;
+    ///
+    /// let cancel = CancellationToken::new();
+    /// // e.g. hand `cancel.clone()` to the code watching for client disconnects
+    /// let result = db.query_cancellable("SELECT * FROM users", (), &cancel).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query_cancellable<P: Into<Params>>(
+        &self,
+        sql: &str,
+        params: P,
+        cancel: &CancellationToken,
+    ) -> Result<QueryResult> {
+        run_cancellable(self.query(sql, params), cancel).await
+    }
+
+    /// Like [`BunnyDbClient::execute`], but aborts the in-flight request and
+    /// returns [`BunnyDbError::Cancelled`] once `cancel` is triggered.
+    ///
+    /// **Not available on `wasm32` targets.**
+    pub async fn execute_cancellable<P: Into<Params>>(
+        &self,
+        sql: &str,
+        params: P,
+        cancel: &CancellationToken,
+    ) -> Result<ExecResult> {
+        run_cancellable(self.execute(sql, params), cancel).await
+    }
+}
+
+/// Races `future` against `cancel`; dropping the losing request future
+/// aborts the underlying HTTP call.
+async fn run_cancellable<T>(
+    future: impl Future<Output = Result<T>>,
+    cancel: &CancellationToken,
+) -> Result<T> {
+    tokio::select! {
+        biased;
+        _ = cancel.cancelled() => Err(BunnyDbError::Cancelled),
+        result = future => result,
+    }
+}
@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+/// Measures elapsed wall-clock time.
+///
+/// `std::time::Instant` panics on `wasm32-unknown-unknown`, so WASM uses the
+/// JS clock instead.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Stopwatch {
+    #[cfg(not(target_arch = "wasm32"))]
+    started: std::time::Instant,
+    #[cfg(target_arch = "wasm32")]
+    started_ms: f64,
+}
+
+impl Stopwatch {
+    pub(crate) fn start() -> Self {
+        Self {
+            #[cfg(not(target_arch = "wasm32"))]
+            started: std::time::Instant::now(),
+            #[cfg(target_arch = "wasm32")]
+            started_ms: js_sys::Date::now(),
+        }
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.started.elapsed()
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            Duration::from_secs_f64((js_sys::Date::now() - self.started_ms).max(0.0) / 1000.0)
+        }
+    }
+}
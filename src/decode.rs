@@ -1,65 +1,143 @@
 use crate::{
+    error::sql_snippet,
+    sql::scan_placeholder_styles,
     wire::{self, ExecuteStatement, NamedArg},
-    BunnyDbError, Col, ExecResult, Params, QueryResult, Value,
+    Base64Mode, BunnyDbError, ClientOptions, Col, ExecResult, NonFiniteFloatPolicy, Params,
+    QueryResult, Value,
 };
 
 pub(crate) fn build_execute_statement(
     sql: &str,
     params: Params,
     want_rows: bool,
+    options: &ClientOptions,
 ) -> Result<ExecuteStatement, BunnyDbError> {
-    match params {
-        Params::Positional(values) => {
-            let args = values
-                .into_iter()
-                .map(encode_value)
-                .collect::<Result<Vec<_>, _>>()?;
-
-            Ok(ExecuteStatement {
-                sql: sql.to_owned(),
-                args: (!args.is_empty()).then_some(args),
-                named_args: None,
-                want_rows,
-            })
+    if options.check_param_style {
+        check_param_style(sql, &params)?;
+    }
+
+    let (args, named_args) = match params {
+        Params::Positional(values) => (encode_positional(values, options)?, Vec::new()),
+        Params::Named(values) => (Vec::new(), encode_named(values, options)?),
+        Params::Mixed { positional, named } => (
+            encode_positional(positional, options)?,
+            encode_named(named, options)?,
+        ),
+    };
+
+    Ok(ExecuteStatement {
+        sql: Some(sql.to_owned()),
+        sql_id: None,
+        args: (!args.is_empty()).then_some(args),
+        named_args: (!named_args.is_empty()).then_some(named_args),
+        want_rows,
+    })
+}
+
+/// Rejects parameters whose variant clearly contradicts the SQL placeholders.
+///
+/// Only the unambiguous cases fail: SQL using both styles, no placeholders
+/// at all, or empty parameters are left to the server.
+fn check_param_style(sql: &str, params: &Params) -> Result<(), BunnyDbError> {
+    let styles = scan_placeholder_styles(sql);
+    let message = match params {
+        Params::Named(values) if !values.is_empty() && styles.positional && !styles.named => {
+            "named parameters supplied, but the SQL only has positional `?` placeholders"
         }
-        Params::Named(values) => {
-            let named_args = values
-                .into_iter()
-                .map(|(name, value)| {
-                    let name = normalize_named_parameter_name(&name)?;
-                    let value = encode_value(value)?;
-                    Ok(NamedArg { name, value })
-                })
-                .collect::<Result<Vec<_>, BunnyDbError>>()?;
-
-            Ok(ExecuteStatement {
-                sql: sql.to_owned(),
-                args: None,
-                named_args: (!named_args.is_empty()).then_some(named_args),
-                want_rows,
-            })
+        Params::Positional(values) if !values.is_empty() && styles.named && !styles.positional => {
+            "positional parameters supplied, but the SQL only has named placeholders"
+        }
+        _ => return Ok(()),
+    };
+    Err(BunnyDbError::ParamStyleMismatch {
+        message: message.to_owned(),
+        sql: sql_snippet(sql),
+    })
+}
+
+fn encode_positional(
+    values: Vec<Value>,
+    options: &ClientOptions,
+) -> Result<Vec<wire::Value>, BunnyDbError> {
+    values
+        .into_iter()
+        .map(|value| encode_value(value, options))
+        .collect()
+}
+
+/// Encodes named arguments in the order given.
+///
+/// Names are compared after stripping the `:`/`@`/`$` prefix, so `:id` and
+/// `@id` bind the same parameter; any repeated name is rejected rather than
+/// letting one value silently shadow another.
+fn encode_named(
+    values: Vec<(String, Value)>,
+    options: &ClientOptions,
+) -> Result<Vec<NamedArg>, BunnyDbError> {
+    let mut args: Vec<NamedArg> = Vec::with_capacity(values.len());
+    for (raw_name, value) in values {
+        let name = normalize_named_parameter_name(&raw_name)?;
+        if args.iter().any(|arg| arg.name == name) {
+            return Err(BunnyDbError::Decode(format!(
+                "duplicate named parameter `{raw_name}` (normalized to `{name}`)"
+            )));
         }
+        let value = encode_value(value, options)?;
+        args.push(NamedArg { name, value });
     }
+    Ok(args)
 }
 
+pub(crate) fn decode_response_payload<T: serde::de::DeserializeOwned>(
+    payload: serde_json::Value,
+    kind: &str,
+    request_index: usize,
+) -> Result<T, BunnyDbError> {
+    serde_json::from_value(payload).map_err(|err| {
+        BunnyDbError::Decode(format!(
+            "invalid {kind} result payload at request {request_index}: {err}"
+        ))
+    })
+}
+
+/// Decodes an execute result into a [`QueryResult`].
+///
+/// Some statements (e.g. certain `PRAGMA`s) return rows without column
+/// metadata. In that case placeholder columns `col0`, `col1`, ... are
+/// synthesized, one per value in the widest row, so positional access keeps
+/// working.
+///
+/// Cells are decoded with [`decode_value`] under `options`.
 pub(crate) fn decode_query_result(
     result: wire::ExecuteResult,
+    meta: &wire::ResponseMeta,
+    options: &ClientOptions,
 ) -> Result<QueryResult, BunnyDbError> {
-    let cols = result
-        .cols
-        .into_iter()
-        .map(|col| Col {
-            name: col.name,
-            decltype: col.decltype,
-        })
-        .collect();
+    let cols = if result.cols.is_empty() {
+        let width = result.rows.iter().map(Vec::len).max().unwrap_or(0);
+        (0..width)
+            .map(|index| Col {
+                name: format!("col{index}"),
+                decltype: None,
+            })
+            .collect()
+    } else {
+        result
+            .cols
+            .into_iter()
+            .map(|col| Col {
+                name: col.name,
+                decltype: col.decltype,
+            })
+            .collect()
+    };
 
     let rows = result
         .rows
         .into_iter()
         .map(|row| {
             row.into_iter()
-                .map(decode_value)
+                .map(|value| decode_value(value, options))
                 .collect::<Result<Vec<_>, BunnyDbError>>()
         })
         .collect::<Result<Vec<_>, _>>()?;
@@ -71,10 +149,16 @@ pub(crate) fn decode_query_result(
         rows_read: result.rows_read,
         rows_written: result.rows_written,
         query_duration_ms: result.query_duration_ms,
+        elapsed: meta.elapsed,
+        baton: meta.baton.clone(),
+        base_url: meta.base_url.clone(),
     })
 }
 
-pub(crate) fn decode_exec_result(result: wire::ExecuteResult) -> Result<ExecResult, BunnyDbError> {
+pub(crate) fn decode_exec_result(
+    result: wire::ExecuteResult,
+    meta: &wire::ResponseMeta,
+) -> Result<ExecResult, BunnyDbError> {
     let last_insert_rowid = result
         .last_insert_rowid
         .map(|value| {
@@ -90,34 +174,57 @@ pub(crate) fn decode_exec_result(result: wire::ExecuteResult) -> Result<ExecResu
         replication_index: result.replication_index,
         rows_read: result.rows_read,
         rows_written: result.rows_written,
+        elapsed: meta.elapsed,
+        baton: meta.baton.clone(),
+        base_url: meta.base_url.clone(),
     })
 }
 
-pub(crate) fn decode_value(value: wire::Value) -> Result<Value, BunnyDbError> {
+/// Decodes a wire value.
+///
+/// Integers outside the `i64` range and non-finite or malformed floats are
+/// errors, unless `options.decode.lenient` is set, in which case they decode
+/// to [`Value::Text`] holding the raw wire string. Blobs are re-encoded from
+/// the wire's standard base64 into `options.base64_mode`.
+pub(crate) fn decode_value(
+    value: wire::Value,
+    options: &ClientOptions,
+) -> Result<Value, BunnyDbError> {
+    let lenient = options.decode.lenient;
     match value {
         wire::Value::Null {} => Ok(Value::Null),
-        wire::Value::Integer { value } => value
-            .parse::<i64>()
-            .map(Value::Integer)
-            .map_err(|err| BunnyDbError::Decode(format!("invalid integer value '{value}': {err}"))),
-        wire::Value::Float { value } => value
-            .parse::<f64>()
-            .map_err(|err| BunnyDbError::Decode(format!("invalid float value '{value}': {err}")))
-            .and_then(|parsed| {
-                if parsed.is_finite() {
-                    Ok(Value::Float(parsed))
-                } else {
-                    Err(BunnyDbError::Decode(format!(
-                        "non-finite float value '{value}' is unsupported"
-                    )))
-                }
-            }),
+        wire::Value::Integer { value } => match value.parse::<i64>() {
+            Ok(parsed) => Ok(Value::Integer(parsed)),
+            Err(_) if lenient => Ok(Value::Text(value)),
+            Err(err) => Err(BunnyDbError::Decode(format!(
+                "invalid integer value '{value}': {err}"
+            ))),
+        },
+        wire::Value::Float { value } => match value.parse::<f64>() {
+            Ok(parsed) if parsed.is_finite() => Ok(Value::Float(parsed)),
+            _ if lenient => Ok(Value::Text(value)),
+            Ok(_) => Err(BunnyDbError::Decode(format!(
+                "non-finite float value '{value}' is unsupported"
+            ))),
+            Err(err) => Err(BunnyDbError::Decode(format!(
+                "invalid float value '{value}': {err}"
+            ))),
+        },
         wire::Value::Text { value } => Ok(Value::Text(value)),
-        wire::Value::Blob { base64 } => Ok(Value::BlobBase64(base64)),
+        wire::Value::Blob { base64 } => {
+            match transcode_base64(&base64, Base64Mode::Standard, options.base64_mode) {
+                Ok(blob) => Ok(Value::BlobBase64(blob)),
+                Err(_) if lenient => Ok(Value::BlobBase64(base64)),
+                Err(BunnyDbError::Decode(message)) => {
+                    Err(BunnyDbError::Decode(format!("blob in result: {message}")))
+                }
+                Err(err) => Err(err),
+            }
+        }
     }
 }
 
-fn encode_value(value: Value) -> Result<wire::Value, BunnyDbError> {
+fn encode_value(value: Value, options: &ClientOptions) -> Result<wire::Value, BunnyDbError> {
     match value {
         Value::Null => Ok(wire::Value::Null {}),
         Value::Integer(value) => Ok(wire::Value::Integer {
@@ -125,19 +232,47 @@ fn encode_value(value: Value) -> Result<wire::Value, BunnyDbError> {
         }),
         Value::Float(value) => {
             if !value.is_finite() {
-                return Err(BunnyDbError::Decode(format!(
-                    "non-finite float value '{value}' is unsupported"
-                )));
+                return match options.non_finite_float {
+                    NonFiniteFloatPolicy::Error => Err(BunnyDbError::Decode(format!(
+                        "non-finite float value '{value}' is unsupported"
+                    ))),
+                    NonFiniteFloatPolicy::Null => Ok(wire::Value::Null {}),
+                    NonFiniteFloatPolicy::Text => Ok(wire::Value::Text {
+                        value: value.to_string(),
+                    }),
+                };
             }
+            // Shortest text that parses back to the same f64, with an
+            // exponent for large or tiny magnitudes instead of hundreds of
+            // digits (`1e300` rather than `1000...0`).
             Ok(wire::Value::Float {
-                value: value.to_string(),
+                value: ryu::Buffer::new().format_finite(value).to_owned(),
             })
         }
         Value::Text(value) => Ok(wire::Value::Text { value }),
-        Value::BlobBase64(base64) => Ok(wire::Value::Blob { base64 }),
+        Value::BlobBase64(blob) => {
+            let base64 = transcode_base64(&blob, options.base64_mode, Base64Mode::Standard)
+                .map_err(|err| match err {
+                    BunnyDbError::Decode(message) => {
+                        BunnyDbError::Decode(format!("blob parameter: {message}"))
+                    }
+                    other => other,
+                })?;
+            Ok(wire::Value::Blob { base64 })
+        }
     }
 }
 
+/// Re-encodes base64 text between alphabets; the input is returned as is
+/// when both modes match.
+fn transcode_base64(input: &str, from: Base64Mode, to: Base64Mode) -> Result<String, BunnyDbError> {
+    if from == to {
+        return Ok(input.to_owned());
+    }
+    let bytes = from.decode(input)?;
+    Ok(to.encode(bytes))
+}
+
 fn normalize_named_parameter_name(name: &str) -> Result<String, BunnyDbError> {
     let normalized = name.trim_start_matches([':', '@', '$']);
     if normalized.is_empty() {
@@ -150,7 +285,26 @@ fn normalize_named_parameter_name(name: &str) -> Result<String, BunnyDbError> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{decode, wire, BunnyDbError, Params, Value};
+    use std::time::Duration;
+
+    use crate::{
+        decode, wire, Base64Mode, BunnyDbError, ClientOptions, DecodeOptions, NonFiniteFloatPolicy,
+        Params, Value,
+    };
+
+    fn with_non_finite(policy: NonFiniteFloatPolicy) -> ClientOptions {
+        ClientOptions {
+            non_finite_float: policy,
+            ..ClientOptions::default()
+        }
+    }
+
+    fn lenient() -> ClientOptions {
+        ClientOptions {
+            decode: DecodeOptions::lenient(),
+            ..ClientOptions::default()
+        }
+    }
 
     #[test]
     fn build_positional_stmt() {
@@ -158,6 +312,7 @@ mod tests {
             "SELECT ?",
             Params::positional([Value::integer(1)]),
             true,
+            &ClientOptions::default(),
         )
         .expect("must build statement");
         assert!(stmt.args.is_some());
@@ -170,6 +325,7 @@ mod tests {
             "SELECT :name",
             Params::named([(":name", Value::text("kit"))]),
             true,
+            &ClientOptions::default(),
         )
         .expect("must build statement");
 
@@ -177,59 +333,396 @@ mod tests {
         assert_eq!(args[0].name, "name");
     }
 
+    #[test]
+    fn build_named_stmt_preserves_order() {
+        let stmt = decode::build_execute_statement(
+            "SELECT :b, :a, :c",
+            Params::named([
+                ("b", Value::integer(2)),
+                ("@a", Value::integer(1)),
+                ("$c", Value::integer(3)),
+            ]),
+            true,
+            &ClientOptions::default(),
+        )
+        .expect("must build statement");
+
+        let names: Vec<_> = stmt
+            .named_args
+            .expect("must contain named args")
+            .into_iter()
+            .map(|arg| arg.name)
+            .collect();
+        assert_eq!(names, ["b", "a", "c"]);
+    }
+
+    #[test]
+    fn build_named_stmt_rejects_duplicates() {
+        for params in [
+            Params::named([(":id", Value::integer(1)), (":id", Value::integer(2))]),
+            Params::named([(":id", Value::integer(1)), ("@id", Value::integer(2))]),
+            Params::mixed(
+                [Value::integer(0)],
+                [("id", Value::integer(1)), ("$id", Value::integer(2))],
+            ),
+        ] {
+            let err = decode::build_execute_statement(
+                "SELECT :id",
+                params,
+                true,
+                &ClientOptions::default(),
+            )
+            .expect_err("duplicate names must be rejected");
+            assert!(
+                matches!(&err, BunnyDbError::Decode(message) if message.contains("duplicate named parameter")),
+                "unexpected error: {err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn build_mixed_stmt_serializes_both_arg_kinds() {
+        let stmt = decode::build_execute_statement(
+            "SELECT * FROM users WHERE id = ? AND name = :name",
+            Params::mixed([Value::integer(1)], [("name", Value::text("kit"))]),
+            true,
+            &ClientOptions::default(),
+        )
+        .expect("must build statement");
+
+        let json = serde_json::to_value(&stmt).expect("must serialize");
+        assert_eq!(
+            json["args"],
+            serde_json::json!([{ "type": "integer", "value": "1" }])
+        );
+        assert_eq!(
+            json["named_args"],
+            serde_json::json!([{ "name": "name", "value": { "type": "text", "value": "kit" } }])
+        );
+    }
+
+    fn checking_param_style() -> ClientOptions {
+        ClientOptions {
+            check_param_style: true,
+            ..ClientOptions::default()
+        }
+    }
+
+    #[test]
+    fn param_style_check_rejects_clear_mismatches() {
+        let named_for_positional = decode::build_execute_statement(
+            "SELECT * FROM users WHERE id = ?",
+            Params::named([("id", Value::integer(1))]),
+            true,
+            &checking_param_style(),
+        )
+        .expect_err("named params for `?` SQL must fail");
+        assert!(matches!(
+            named_for_positional,
+            BunnyDbError::ParamStyleMismatch { ref sql, .. } if sql == "SELECT * FROM users WHERE id = ?"
+        ));
+
+        let positional_for_named = decode::build_execute_statement(
+            "SELECT * FROM users WHERE id = :id",
+            Params::positional([Value::integer(1)]),
+            true,
+            &checking_param_style(),
+        )
+        .expect_err("positional params for `:id` SQL must fail");
+        assert!(matches!(
+            positional_for_named,
+            BunnyDbError::ParamStyleMismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn param_style_check_allows_matching_and_mixed_sql() {
+        for (sql, params) in [
+            (
+                "SELECT * FROM t WHERE a = ? AND b = :b",
+                Params::named([("b", Value::integer(1))]),
+            ),
+            (
+                "SELECT * FROM t WHERE a = ? AND b = :b",
+                Params::positional([Value::integer(1)]),
+            ),
+            (
+                "SELECT * FROM t WHERE a = @a",
+                Params::named([("a", Value::integer(1))]),
+            ),
+            (
+                "SELECT ':a' WHERE 1 = ?",
+                Params::positional([Value::integer(1)]),
+            ),
+            ("SELECT 1", Params::named([("a", Value::integer(1))])),
+        ] {
+            decode::build_execute_statement(sql, params, true, &checking_param_style())
+                .unwrap_or_else(|err| panic!("{sql:?} must pass: {err}"));
+        }
+    }
+
+    #[test]
+    fn param_style_check_is_off_by_default() {
+        decode::build_execute_statement(
+            "SELECT * FROM users WHERE id = ?",
+            Params::named([("id", Value::integer(1))]),
+            true,
+            &ClientOptions::default(),
+        )
+        .expect("no check without the option");
+    }
+
     #[test]
     fn build_rejects_non_finite_float() {
         let err = decode::build_execute_statement(
             "SELECT ?",
             Params::positional([Value::float(f64::NAN)]),
             true,
+            &ClientOptions::default(),
         )
         .expect_err("must fail");
 
         assert!(matches!(err, BunnyDbError::Decode(_)));
     }
 
+    #[test]
+    fn build_binds_non_finite_float_as_null() {
+        let stmt = decode::build_execute_statement(
+            "SELECT ?, ?",
+            Params::positional([Value::float(f64::NAN), Value::float(1.5)]),
+            true,
+            &with_non_finite(NonFiniteFloatPolicy::Null),
+        )
+        .expect("must build statement");
+
+        let args = stmt.args.expect("args");
+        assert!(matches!(args[0], wire::Value::Null {}));
+        assert!(matches!(&args[1], wire::Value::Float { value } if value == "1.5"));
+    }
+
+    #[test]
+    fn build_binds_non_finite_float_as_text() {
+        let stmt = decode::build_execute_statement(
+            "SELECT :a, :b, :c",
+            Params::named([
+                ("a", Value::float(f64::NAN)),
+                ("b", Value::float(f64::INFINITY)),
+                ("c", Value::float(f64::NEG_INFINITY)),
+            ]),
+            true,
+            &with_non_finite(NonFiniteFloatPolicy::Text),
+        )
+        .expect("must build statement");
+
+        let texts: Vec<_> = stmt
+            .named_args
+            .expect("named args")
+            .into_iter()
+            .map(|arg| match arg.value {
+                wire::Value::Text { value } => value,
+                other => panic!("expected text, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(texts, ["NaN", "inf", "-inf"]);
+    }
+
+    #[test]
+    fn floats_encode_as_shortest_round_trip_text() {
+        let cases = [
+            (0.3, "0.3"),
+            (0.1 + 0.2, "0.30000000000000004"),
+            (1.0, "1.0"),
+            (-2.5, "-2.5"),
+            (1e300, "1e300"),
+            (-1.25e-300, "-1.25e-300"),
+            (f64::MAX, "1.7976931348623157e308"),
+            (5e-324, "5e-324"),
+        ];
+        let options = ClientOptions::default();
+        for (float, expected) in cases {
+            let encoded = decode::encode_value(Value::float(float), &options).expect("encode");
+            assert_eq!(
+                encoded,
+                wire::Value::Float {
+                    value: expected.to_owned()
+                }
+            );
+            let decoded = decode::decode_value(encoded, &options).expect("decode");
+            assert!(
+                matches!(decoded, Value::Float(back) if back.to_bits() == float.to_bits()),
+                "{float:e} must round-trip, got {decoded:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn blob_parameters_are_sent_as_standard_base64() {
+        let url_safe = ClientOptions {
+            base64_mode: Base64Mode::UrlSafe,
+            ..ClientOptions::default()
+        };
+        let stmt = decode::build_execute_statement(
+            "SELECT ?",
+            Params::positional([Value::blob_with_mode(
+                [0xfb, 0xff, 0x3e],
+                Base64Mode::UrlSafe,
+            )]),
+            true,
+            &url_safe,
+        )
+        .expect("must build statement");
+        let args = stmt.args.expect("args");
+        assert!(matches!(&args[0], wire::Value::Blob { base64 } if base64 == "+/8+"));
+
+        let err = decode::build_execute_statement(
+            "SELECT ?",
+            Params::positional([Value::blob_base64("+/8+")]),
+            true,
+            &url_safe,
+        )
+        .expect_err("standard payload is not valid URL-safe base64");
+        assert!(matches!(err, BunnyDbError::Decode(message) if message.contains("blob parameter")));
+    }
+
+    #[test]
+    fn result_blobs_use_configured_base64_mode() {
+        let blob = || wire::Value::Blob {
+            base64: "+/8+".to_owned(),
+        };
+        let url_safe_no_pad = ClientOptions {
+            base64_mode: Base64Mode::UrlSafeNoPad,
+            ..ClientOptions::default()
+        };
+
+        let value = decode::decode_value(blob(), &url_safe_no_pad).expect("must decode");
+        assert_eq!(value, Value::blob_base64("-_8-"));
+        assert_eq!(
+            value.as_bytes_with_mode(Base64Mode::UrlSafeNoPad),
+            Some(vec![0xfb, 0xff, 0x3e])
+        );
+        assert_eq!(
+            decode::decode_value(blob(), &ClientOptions::default()).expect("must decode"),
+            Value::blob_base64("+/8+")
+        );
+    }
+
     #[test]
     fn decode_integer_parse_error() {
         let value = wire::Value::Integer {
             value: "nope".to_owned(),
         };
-        let err = decode::decode_value(value).expect_err("must fail");
+        let err = decode::decode_value(value, &ClientOptions::default()).expect_err("must fail");
         assert!(matches!(err, BunnyDbError::Decode(_)));
     }
 
-    #[test]
-    fn decode_query_result_preserves_telemetry() {
-        let decoded = decode::decode_query_result(wire::ExecuteResult {
+    fn overflowing_row() -> wire::ExecuteResult {
+        wire::ExecuteResult {
             cols: vec![],
-            rows: vec![],
+            rows: vec![vec![
+                wire::Value::Integer {
+                    value: "7".to_owned(),
+                },
+                wire::Value::Integer {
+                    value: "18446744073709551616".to_owned(),
+                },
+                wire::Value::Float {
+                    value: "1e400".to_owned(),
+                },
+            ]],
             affected_row_count: 0,
             last_insert_rowid: None,
-            replication_index: Some("42".to_owned()),
-            rows_read: Some(11),
-            rows_written: Some(3),
-            query_duration_ms: Some(1.75),
-        })
+            replication_index: None,
+            rows_read: None,
+            rows_written: None,
+            query_duration_ms: None,
+        }
+    }
+
+    #[test]
+    fn strict_decoding_rejects_overflowing_cells() {
+        let err = decode::decode_query_result(
+            overflowing_row(),
+            &wire::ResponseMeta::default(),
+            &ClientOptions::default(),
+        )
+        .expect_err("strict mode must fail");
+        assert!(
+            matches!(err, BunnyDbError::Decode(message) if message.contains("18446744073709551616"))
+        );
+
+        let err = decode::decode_value(
+            wire::Value::Float {
+                value: "1e400".to_owned(),
+            },
+            &ClientOptions::default(),
+        )
+        .expect_err("non-finite float must fail");
+        assert!(matches!(err, BunnyDbError::Decode(_)));
+    }
+
+    #[test]
+    fn lenient_decoding_keeps_raw_text() {
+        let decoded = decode::decode_query_result(
+            overflowing_row(),
+            &wire::ResponseMeta::default(),
+            &lenient(),
+        )
+        .expect("lenient mode must decode");
+        assert_eq!(
+            decoded.rows[0],
+            vec![
+                Value::Integer(7),
+                Value::Text("18446744073709551616".to_owned()),
+                Value::Text("1e400".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_query_result_preserves_telemetry() {
+        let decoded = decode::decode_query_result(
+            wire::ExecuteResult {
+                cols: vec![],
+                rows: vec![],
+                affected_row_count: 0,
+                last_insert_rowid: None,
+                replication_index: Some("42".to_owned()),
+                rows_read: Some(11),
+                rows_written: Some(3),
+                query_duration_ms: Some(1.75),
+            },
+            &wire::ResponseMeta {
+                baton: Some("b-1".to_owned()),
+                base_url: None,
+                elapsed: Some(Duration::from_millis(12)),
+            },
+            &ClientOptions::default(),
+        )
         .expect("must decode");
 
         assert_eq!(decoded.replication_index.as_deref(), Some("42"));
         assert_eq!(decoded.rows_read, Some(11));
         assert_eq!(decoded.rows_written, Some(3));
         assert_eq!(decoded.query_duration_ms, Some(1.75));
+        assert_eq!(decoded.elapsed, Some(Duration::from_millis(12)));
+        assert_eq!(decoded.baton.as_deref(), Some("b-1"));
     }
 
     #[test]
     fn decode_exec_result_preserves_telemetry() {
-        let decoded = decode::decode_exec_result(wire::ExecuteResult {
-            cols: vec![],
-            rows: vec![],
-            affected_row_count: 1,
-            last_insert_rowid: Some("7".to_owned()),
-            replication_index: Some("43".to_owned()),
-            rows_read: Some(2),
-            rows_written: Some(1),
-            query_duration_ms: Some(0.25),
-        })
+        let decoded = decode::decode_exec_result(
+            wire::ExecuteResult {
+                cols: vec![],
+                rows: vec![],
+                affected_row_count: 1,
+                last_insert_rowid: Some("7".to_owned()),
+                replication_index: Some("43".to_owned()),
+                rows_read: Some(2),
+                rows_written: Some(1),
+                query_duration_ms: Some(0.25),
+            },
+            &wire::ResponseMeta::default(),
+        )
         .expect("must decode");
 
         assert_eq!(decoded.affected_row_count, 1);
@@ -238,4 +731,54 @@ mod tests {
         assert_eq!(decoded.rows_read, Some(2));
         assert_eq!(decoded.rows_written, Some(1));
     }
+
+    #[test]
+    fn decode_accepts_numeric_counters_and_values() {
+        let result: wire::ExecuteResult = serde_json::from_value(serde_json::json!({
+            "affected_row_count": "3",
+            "last_insert_rowid": 9
+        }))
+        .expect("numeric fields must deserialize");
+        let exec = decode::decode_exec_result(result, &wire::ResponseMeta::default())
+            .expect("must decode");
+        assert_eq!(exec.affected_row_count, 3);
+        assert_eq!(exec.last_insert_rowid, Some(9));
+
+        let query: wire::ExecuteResult = serde_json::from_value(serde_json::json!({
+            "cols": [{ "name": "n" }, { "name": "x" }],
+            "rows": [[
+                { "type": "integer", "value": 42 },
+                { "type": "float", "value": 1.5 }
+            ]]
+        }))
+        .expect("numeric values must deserialize");
+        let decoded = decode::decode_query_result(
+            query,
+            &wire::ResponseMeta::default(),
+            &ClientOptions::default(),
+        )
+        .expect("must decode");
+        assert_eq!(decoded.rows[0], vec![Value::Integer(42), Value::Float(1.5)]);
+    }
+
+    #[test]
+    fn decode_query_result_synthesizes_missing_cols() {
+        let result: wire::ExecuteResult = serde_json::from_value(serde_json::json!({
+            "rows": [[
+                { "type": "integer", "value": "0" },
+                { "type": "text", "value": "main" }
+            ]]
+        }))
+        .expect("rows without cols must deserialize");
+        let decoded = decode::decode_query_result(
+            result,
+            &wire::ResponseMeta::default(),
+            &ClientOptions::default(),
+        )
+        .expect("must decode");
+
+        let names: Vec<&str> = decoded.cols.iter().map(|col| col.name.as_str()).collect();
+        assert_eq!(names, ["col0", "col1"]);
+        assert_eq!(decoded.column_index("col1"), Some(1));
+    }
 }
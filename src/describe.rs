@@ -0,0 +1,66 @@
+use crate::{
+    decode::decode_response_payload,
+    wire::{self, PipelineRequest, Request},
+    BunnyDbClient, BunnyDbError, Col, Result,
+};
+
+impl BunnyDbClient {
+    /// Returns the output columns of `sql` without executing it.
+    ///
+    /// Sends a pipeline `describe` request, so parameterized statements can
+    /// be inspected without binding values and without side effects.
+    /// Statements that return no rows (e.g. `INSERT` without `RETURNING`)
+    /// yield an empty list.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run(db: bunnydb_http::BunnyDbClient) -> bunnydb_http::Result<()> {
+    /// let cols = db.describe("SELECT id, name FROM users WHERE id = ?").await?;
+    /// for col in cols {
+    ///     println!("{} {:?}", col.name, col.decltype);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn describe(&self, sql: &str) -> Result<Vec<Col>> {
+        let payload = PipelineRequest {
+            baton: None,
+            requests: vec![
+                Request::Describe {
+                    sql: sql.to_owned(),
+                },
+                Request::Close {},
+            ],
+        };
+        let response = self.send_pipeline_with_retry(&payload).await?;
+
+        if response.results.len() != 2 {
+            return Err(BunnyDbError::Decode(format!(
+                "result count mismatch: expected 2, got {}",
+                response.results.len()
+            )));
+        }
+
+        let mut iter = response.results.into_iter();
+        let describe = iter
+            .next()
+            .ok_or_else(|| BunnyDbError::Decode("missing describe result".to_owned()))?;
+        let close = iter
+            .next()
+            .ok_or_else(|| BunnyDbError::Decode("missing close result".to_owned()))?;
+
+        let payload = Self::into_response_payload(describe, 0, "describe", Some(sql))?;
+        let result: wire::DescribeResult = decode_response_payload(payload, "describe", 0)?;
+        Self::ensure_close_success(close, 1)?;
+
+        Ok(result
+            .cols
+            .into_iter()
+            .map(|col| Col {
+                name: col.name,
+                decltype: col.decltype,
+            })
+            .collect())
+    }
+}
@@ -1,14 +1,74 @@
+/// Maximum number of characters of SQL text or response body retained in
+/// error messages.
+const SNIPPET_MAX_CHARS: usize = 200;
+
 /// Error type returned by this crate.
+///
+/// New variants may be added in minor releases, so `match`es outside this
+/// crate need a wildcard arm.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum BunnyDbError {
     /// Network or request execution error from `reqwest`.
-    #[error("transport error: {0}")]
-    Transport(reqwest::Error),
+    #[error("transport error{}: {source}", attempts_suffix(*.attempts))]
+    Transport {
+        /// Underlying `reqwest` error from the last attempt.
+        source: reqwest::Error,
+        /// Number of HTTP attempts made, including the initial one.
+        attempts: usize,
+    },
     /// Non-success HTTP status code with raw response body.
-    #[error("http error {status}: {body}")]
-    Http { status: u16, body: String },
+    ///
+    /// The message shows a truncated body; the field keeps all of it.
+    #[error("http error {status}{}: {}", attempts_suffix(*.attempts), body_snippet(.body))]
+    Http {
+        /// HTTP status code of the last attempt.
+        status: u16,
+        /// Response body of the last attempt.
+        body: String,
+        /// Number of HTTP attempts made, including the initial one.
+        attempts: usize,
+    },
+    /// Non-success HTTP status whose body is a structured `{message, code}`
+    /// error, e.g. a `400` for a SQL error reported at the HTTP level.
+    ///
+    /// Bodies without that shape are reported as [`BunnyDbError::Http`].
+    #[error(
+        "api error {status}{}: {message}{}",
+        attempts_suffix(*.attempts),
+        format_code_suffix(.code)
+    )]
+    Api {
+        /// HTTP status code of the last attempt.
+        status: u16,
+        /// Error message from the response body.
+        message: String,
+        /// Optional engine-specific error code, e.g. `SQLITE_ERROR`.
+        code: Option<String>,
+        /// Number of HTTP attempts made, including the initial one.
+        attempts: usize,
+    },
+    /// Successful HTTP status with a body that is not JSON, typically an
+    /// HTML page from a proxy in front of the database.
+    #[error(
+        "non-JSON response (status {status}, content-type {}): {snippet}",
+        .content_type.as_deref().unwrap_or("unknown")
+    )]
+    NonJsonResponse {
+        /// HTTP status code.
+        status: u16,
+        /// `Content-Type` header, if present.
+        content_type: Option<String>,
+        /// Start of the body, truncated to 200 characters.
+        snippet: String,
+        /// Full response body, for debugging.
+        body: String,
+    },
     /// SQL/pipeline error returned by Bunny.net API.
-    #[error("pipeline error at request {request_index}: {message}")]
+    #[error(
+        "pipeline error at request {request_index}: {message}{}",
+        format_sql_suffix(.sql)
+    )]
     Pipeline {
         /// Index of the failing request in the pipeline payload.
         request_index: usize,
@@ -16,8 +76,377 @@ pub enum BunnyDbError {
         message: String,
         /// Optional engine-specific error code.
         code: Option<String>,
+        /// SQL text of the failing statement, truncated to 200 characters.
+        sql: Option<String>,
+    },
+    /// The server no longer knows the stream (baton) a request referred to,
+    /// typically because it expired after a period of inactivity.
+    ///
+    /// The failed request did not run. A [`crate::PreparedStatement`] starts
+    /// a fresh stream on its next call, so retrying it is safe. Work that
+    /// lives only on the stream, such as uncommitted writes in a session, is
+    /// lost and cannot be resumed; start over from the beginning instead.
+    #[error("session expired: {message}")]
+    SessionExpired {
+        /// Server message, truncated to 200 characters.
+        message: String,
     },
     /// Response decoding or protocol-shape validation error.
     #[error("decode error: {0}")]
     Decode(String),
+    /// Supplied [`crate::Params`] contradict the placeholder style of the
+    /// SQL; only reported when [`crate::ClientOptions::check_param_style`]
+    /// is set.
+    #[error("parameter style mismatch: {message} (sql: {sql})")]
+    ParamStyleMismatch {
+        /// Which style was supplied and which the SQL uses.
+        message: String,
+        /// SQL text of the statement, truncated to 200 characters.
+        sql: String,
+    },
+    /// Serialized request exceeds [`crate::ClientOptions::max_request_bytes`].
+    #[error("request body of {size} bytes exceeds the {limit} byte limit")]
+    RequestTooLarge {
+        /// Serialized JSON size in bytes.
+        size: usize,
+        /// Configured limit in bytes.
+        limit: usize,
+    },
+    /// Request aborted through a cancellation token.
+    #[error("request cancelled")]
+    Cancelled,
+    /// Request rejected because the circuit breaker is open.
+    #[error("circuit breaker open; retry after {retry_after_ms} ms")]
+    CircuitOpen {
+        /// Remaining cooldown before a probe request is allowed.
+        retry_after_ms: u64,
+    },
+    /// Invalid client configuration, such as a malformed database URL.
+    #[error("configuration error: {0}")]
+    Config(String),
+}
+
+impl BunnyDbError {
+    /// Creates a [`BunnyDbError::Decode`] error, e.g. from a custom row
+    /// conversion.
+    pub fn decode(message: impl Into<String>) -> Self {
+        Self::Decode(message.into())
+    }
+
+    /// Creates a [`BunnyDbError::Config`] error.
+    pub fn config(message: impl Into<String>) -> Self {
+        Self::Config(message.into())
+    }
+
+    /// HTTP status of an [`BunnyDbError::Http`] or [`BunnyDbError::Api`]
+    /// error.
+    pub fn http_status(&self) -> Option<u16> {
+        match self {
+            Self::Http { status, .. } | Self::Api { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Whether this is a SQL error from a `UNIQUE` or `PRIMARY KEY`
+    /// constraint.
+    ///
+    /// Matches the extended codes `SQLITE_CONSTRAINT_UNIQUE` and
+    /// `SQLITE_CONSTRAINT_PRIMARYKEY`. When the server reports only the
+    /// primary `SQLITE_CONSTRAINT` code, the message is checked for SQLite's
+    /// `UNIQUE constraint failed` text instead.
+    pub fn is_unique_violation(&self) -> bool {
+        self.is_constraint_violation(
+            &["SQLITE_CONSTRAINT_UNIQUE", "SQLITE_CONSTRAINT_PRIMARYKEY"],
+            "UNIQUE constraint failed",
+        )
+    }
+
+    /// Whether this is a SQL error from a `FOREIGN KEY` constraint.
+    ///
+    /// Matches the extended code `SQLITE_CONSTRAINT_FOREIGNKEY`, or the
+    /// primary `SQLITE_CONSTRAINT` code with SQLite's
+    /// `FOREIGN KEY constraint failed` message.
+    pub fn is_foreign_key_violation(&self) -> bool {
+        self.is_constraint_violation(
+            &["SQLITE_CONSTRAINT_FOREIGNKEY"],
+            "FOREIGN KEY constraint failed",
+        )
+    }
+
+    /// Converts an error for a request that carried a baton into
+    /// [`BunnyDbError::SessionExpired`] when the server rejected the baton.
+    ///
+    /// Servers report this as HTTP 400/404 mentioning an expired stream or
+    /// an invalid baton, or as a `STREAM_EXPIRED` error code.
+    pub(crate) fn into_session_expired(self) -> Self {
+        let message = match &self {
+            Self::Http {
+                status: 400 | 404,
+                body,
+                ..
+            } if mentions_stale_stream(body) => body_snippet(body),
+            Self::Api {
+                status: 400 | 404,
+                code,
+                message,
+                ..
+            }
+            | Self::Pipeline { code, message, .. }
+                if code
+                    .as_deref()
+                    .is_some_and(|code| code.eq_ignore_ascii_case("STREAM_EXPIRED"))
+                    || mentions_stale_stream(message) =>
+            {
+                body_snippet(message)
+            }
+            _ => return self,
+        };
+        Self::SessionExpired { message }
+    }
+
+    fn is_constraint_violation(&self, extended_codes: &[&str], message_marker: &str) -> bool {
+        let (Self::Pipeline {
+            code: Some(code),
+            message,
+            ..
+        }
+        | Self::Api {
+            code: Some(code),
+            message,
+            ..
+        }) = self
+        else {
+            return false;
+        };
+        if extended_codes
+            .iter()
+            .any(|extended| code.eq_ignore_ascii_case(extended))
+        {
+            return true;
+        }
+        code.eq_ignore_ascii_case("SQLITE_CONSTRAINT")
+            && message
+                .to_ascii_uppercase()
+                .contains(&message_marker.to_ascii_uppercase())
+    }
+}
+
+fn mentions_stale_stream(text: &str) -> bool {
+    let text = text.to_ascii_lowercase();
+    [
+        "stream_expired",
+        "stream expired",
+        "stream has expired",
+        "baton",
+    ]
+    .iter()
+    .any(|marker| text.contains(marker))
+}
+
+/// Truncates SQL text for inclusion in error values.
+pub(crate) fn sql_snippet(sql: &str) -> String {
+    truncate_chars(sql.trim())
+}
+
+/// Truncates a response body for inclusion in error messages.
+pub(crate) fn body_snippet(body: &str) -> String {
+    truncate_chars(body.trim())
+}
+
+fn truncate_chars(text: &str) -> String {
+    match text.char_indices().nth(SNIPPET_MAX_CHARS) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text.to_owned(),
+    }
+}
+
+fn attempts_suffix(attempts: usize) -> String {
+    if attempts > 1 {
+        format!(" after {attempts} attempts")
+    } else {
+        String::new()
+    }
+}
+
+fn format_code_suffix(code: &Option<String>) -> String {
+    match code {
+        Some(code) => format!(" ({code})"),
+        None => String::new(),
+    }
+}
+
+fn format_sql_suffix(sql: &Option<String>) -> String {
+    match sql {
+        Some(sql) => format!(" (sql: {sql})"),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sql_snippet, BunnyDbError};
+
+    #[test]
+    fn sql_snippet_truncates_long_sql() {
+        let sql = format!("SELECT '{}'", "é".repeat(300));
+        let snippet = sql_snippet(&sql);
+        assert!(snippet.ends_with("..."));
+        assert_eq!(snippet.chars().count(), 203);
+    }
+
+    #[test]
+    fn http_display_truncates_body() {
+        let body = format!("<html>{}</html>", "x".repeat(500));
+        let err = BunnyDbError::Http {
+            status: 502,
+            body: body.clone(),
+            attempts: 1,
+        };
+        let message = err.to_string();
+        assert!(message.starts_with("http error 502: <html>xxx"));
+        assert!(message.ends_with("..."));
+        assert!(message.len() < 250);
+        assert!(matches!(err, BunnyDbError::Http { body: full, .. } if full == body));
+    }
+
+    #[test]
+    fn http_display_mentions_retries() {
+        let err = BunnyDbError::Http {
+            status: 503,
+            body: "busy".to_owned(),
+            attempts: 3,
+        };
+        assert_eq!(err.to_string(), "http error 503 after 3 attempts: busy");
+    }
+
+    fn sql_error(code: Option<&str>, message: &str) -> BunnyDbError {
+        BunnyDbError::Pipeline {
+            request_index: 0,
+            message: message.to_owned(),
+            code: code.map(str::to_owned),
+            sql: None,
+        }
+    }
+
+    #[test]
+    fn constraint_violations_match_extended_codes() {
+        let unique = sql_error(
+            Some("SQLITE_CONSTRAINT_UNIQUE"),
+            "UNIQUE constraint failed: users.email",
+        );
+        assert!(unique.is_unique_violation());
+        assert!(!unique.is_foreign_key_violation());
+
+        let primary_key = sql_error(
+            Some("SQLITE_CONSTRAINT_PRIMARYKEY"),
+            "UNIQUE constraint failed: users.id",
+        );
+        assert!(primary_key.is_unique_violation());
+
+        let foreign_key = sql_error(
+            Some("SQLITE_CONSTRAINT_FOREIGNKEY"),
+            "FOREIGN KEY constraint failed",
+        );
+        assert!(foreign_key.is_foreign_key_violation());
+        assert!(!foreign_key.is_unique_violation());
+    }
+
+    #[test]
+    fn constraint_violations_fall_back_to_message_for_primary_code() {
+        assert!(sql_error(
+            Some("SQLITE_CONSTRAINT"),
+            "SQLite error: UNIQUE constraint failed: users.email"
+        )
+        .is_unique_violation());
+        assert!(
+            sql_error(Some("SQLITE_CONSTRAINT"), "FOREIGN KEY constraint failed")
+                .is_foreign_key_violation()
+        );
+
+        let not_null = sql_error(
+            Some("SQLITE_CONSTRAINT"),
+            "NOT NULL constraint failed: users.name",
+        );
+        assert!(!not_null.is_unique_violation());
+        assert!(!not_null.is_foreign_key_violation());
+
+        // Without a constraint code the message alone is not trusted.
+        assert!(!sql_error(None, "UNIQUE constraint failed: users.email").is_unique_violation());
+        assert!(!sql_error(Some("SQLITE_ERROR"), "no such table: users").is_unique_violation());
+        assert!(!BunnyDbError::decode("UNIQUE constraint failed").is_unique_violation());
+    }
+
+    #[test]
+    fn stale_baton_errors_become_session_expired() {
+        let http = |status: u16, body: &str| BunnyDbError::Http {
+            status,
+            body: body.to_owned(),
+            attempts: 1,
+        };
+
+        for err in [
+            http(400, r#"{"error":"stream expired"}"#),
+            http(
+                400,
+                r#"{"message":"The stream has expired due to inactivity","code":"STREAM_EXPIRED"}"#,
+            ),
+            http(404, "Invalid baton"),
+            BunnyDbError::Api {
+                status: 400,
+                message: "The stream has expired due to inactivity".to_owned(),
+                code: Some("STREAM_EXPIRED".to_owned()),
+                attempts: 1,
+            },
+            sql_error(Some("STREAM_EXPIRED"), "stream is gone"),
+        ] {
+            assert!(
+                matches!(
+                    err.into_session_expired(),
+                    BunnyDbError::SessionExpired { .. }
+                ),
+                "must be recognized as expired"
+            );
+        }
+
+        for err in [
+            http(400, "no such table: users"),
+            http(503, "stream expired"),
+            sql_error(Some("SQLITE_ERROR"), "no such table: users"),
+        ] {
+            assert!(!matches!(
+                err.into_session_expired(),
+                BunnyDbError::SessionExpired { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn api_errors_show_code_and_classify_constraints() {
+        let err = BunnyDbError::Api {
+            status: 400,
+            message: "UNIQUE constraint failed: users.email".to_owned(),
+            code: Some("SQLITE_CONSTRAINT_UNIQUE".to_owned()),
+            attempts: 1,
+        };
+        assert_eq!(
+            err.to_string(),
+            "api error 400: UNIQUE constraint failed: users.email (SQLITE_CONSTRAINT_UNIQUE)"
+        );
+        assert_eq!(err.http_status(), Some(400));
+        assert!(err.is_unique_violation());
+    }
+
+    #[test]
+    fn pipeline_display_includes_sql() {
+        let err = BunnyDbError::Pipeline {
+            request_index: 2,
+            message: "no such table: users".to_owned(),
+            code: None,
+            sql: Some(sql_snippet("SELECT * FROM users")),
+        };
+        assert_eq!(
+            err.to_string(),
+            "pipeline error at request 2: no such table: users (sql: SELECT * FROM users)"
+        );
+    }
 }
@@ -0,0 +1,127 @@
+use crate::{sql, BunnyDbClient, BunnyDbError, Params, QueryResult, Result, Value};
+
+/// One row of an `EXPLAIN QUERY PLAN` result.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QueryPlanStep {
+    /// Node id within the plan.
+    pub id: i64,
+    /// Id of the parent node (`0` for top-level steps).
+    pub parent: i64,
+    /// Human-readable description, e.g. `SCAN users`.
+    pub detail: String,
+}
+
+impl BunnyDbClient {
+    /// Runs `EXPLAIN QUERY PLAN <sql>` and decodes the plan rows.
+    ///
+    /// Fails with [`BunnyDbError::Decode`] if `sql` already starts with
+    /// `EXPLAIN` or the result lacks the `id`, `parent` or `detail` columns.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run(db: bunnydb_http::BunnyDbClient) -> bunnydb_http::Result<()> {
+    /// use bunnydb_http::Value;
+    ///
+    /// let plan = db.explain("SELECT * FROM users WHERE id = ?", [Value::integer(1)]).await?;
+    /// for step in plan {
+    ///     println!("{} {} {}", step.id, step.parent, step.detail);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn explain<P: Into<Params>>(
+        &self,
+        sql: &str,
+        params: P,
+    ) -> Result<Vec<QueryPlanStep>> {
+        if sql::scan_words(sql)
+            .first()
+            .is_some_and(|word| word.is("EXPLAIN"))
+        {
+            return Err(BunnyDbError::Decode(
+                "explain expects SQL without an EXPLAIN prefix".to_owned(),
+            ));
+        }
+
+        let result = self
+            .query(&format!("EXPLAIN QUERY PLAN {}", sql.trim()), params)
+            .await?;
+        decode_query_plan(&result)
+    }
+}
+
+fn decode_query_plan(result: &QueryResult) -> Result<Vec<QueryPlanStep>> {
+    let column = |name: &str| {
+        result.column_index(name).ok_or_else(|| {
+            BunnyDbError::Decode(format!("query plan result has no `{name}` column"))
+        })
+    };
+    let id = column("id")?;
+    let parent = column("parent")?;
+    let detail = column("detail")?;
+
+    result
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(index, row)| {
+            Ok(QueryPlanStep {
+                id: plan_integer(row.get(id), index, "id")?,
+                parent: plan_integer(row.get(parent), index, "parent")?,
+                detail: match row.get(detail) {
+                    Some(Value::Text(text)) => text.clone(),
+                    other => {
+                        return Err(BunnyDbError::Decode(format!(
+                            "query plan row {index}: expected text `detail`, got {other:?}"
+                        )))
+                    }
+                },
+            })
+        })
+        .collect()
+}
+
+fn plan_integer(value: Option<&Value>, index: usize, name: &str) -> Result<i64> {
+    match value {
+        Some(Value::Integer(value)) => Ok(*value),
+        other => Err(BunnyDbError::Decode(format!(
+            "query plan row {index}: expected integer `{name}`, got {other:?}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_query_plan, QueryPlanStep};
+    use crate::{BunnyDbError, QueryResult, Value};
+
+    #[test]
+    fn decodes_plan_rows_by_column_name() {
+        let result = QueryResult::fixture(
+            &["id", "parent", "notused", "detail"],
+            vec![vec![
+                Value::integer(2),
+                Value::integer(0),
+                Value::integer(0),
+                Value::text("SCAN users"),
+            ]],
+        );
+
+        assert_eq!(
+            decode_query_plan(&result).expect("plan must decode"),
+            vec![QueryPlanStep {
+                id: 2,
+                parent: 0,
+                detail: "SCAN users".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn missing_detail_column_is_rejected() {
+        let result = QueryResult::fixture(&["id", "parent"], Vec::new());
+        let err = decode_query_plan(&result).expect_err("missing column must fail");
+        assert!(matches!(err, BunnyDbError::Decode(message) if message.contains("detail")));
+    }
+}
@@ -0,0 +1,105 @@
+//! CSV export of query results.
+//!
+//! Enabled with the `csv` feature.
+
+use std::borrow::Cow;
+
+use crate::{QueryResult, Value};
+
+impl QueryResult {
+    /// Renders the result as CSV: a header row of column names, then one
+    /// record per row.
+    ///
+    /// Fields are quoted only when needed (commas, quotes, line breaks), with
+    /// embedded quotes doubled. `NULL` becomes an empty field, floats use the
+    /// shortest round-trip form, and blobs are exported as their base64 text.
+    /// Every record has one field per column: missing cells of short rows
+    /// are written as empty fields and extra cells are dropped. Lines end
+    /// with `\n`.
+    ///
+    /// ```
+    /// # use bunnydb_http::QueryResult;
+    /// # fn export(result: &QueryResult) -> std::io::Result<()> {
+    /// std::fs::write("users.csv", result.to_csv())
+    /// # }
+    /// ```
+    pub fn to_csv(&self) -> String {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer
+            .write_record(self.cols.iter().map(|col| col.name.as_str()))
+            .expect("writing CSV to memory cannot fail");
+        for row in &self.rows {
+            // Fit every record to the header, as `columns()` does, so the
+            // writer never sees a ragged record.
+            let fields: Vec<Cow<'_, str>> = (0..self.cols.len())
+                .map(|index| row.get(index).map_or(Cow::Borrowed(""), csv_field))
+                .collect();
+            writer
+                .write_record(fields.iter().map(|field| field.as_bytes()))
+                .expect("records match the header length");
+        }
+        let bytes = writer
+            .into_inner()
+            .expect("flushing CSV to memory cannot fail");
+        String::from_utf8(bytes).expect("CSV built from UTF-8 fields is UTF-8")
+    }
+}
+
+fn csv_field(value: &Value) -> Cow<'_, str> {
+    match value {
+        Value::Null => Cow::Borrowed(""),
+        Value::Integer(value) => Cow::Owned(value.to_string()),
+        Value::Float(value) if value.is_finite() => {
+            Cow::Owned(ryu::Buffer::new().format_finite(*value).to_owned())
+        }
+        Value::Float(value) => Cow::Owned(value.to_string()),
+        Value::Text(value) | Value::BlobBase64(value) => Cow::Borrowed(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{QueryResult, Value};
+
+    #[test]
+    fn to_csv_quotes_and_escapes_fields() {
+        let result = QueryResult::fixture(
+            &["id", "name", "score", "avatar"],
+            vec![
+                vec![
+                    Value::integer(1),
+                    Value::text("Kit, \"the\" cat"),
+                    Value::float(0.1 + 0.2),
+                    Value::blob_base64("AQID"),
+                ],
+                vec![
+                    Value::integer(2),
+                    Value::text("line\nbreak"),
+                    Value::null(),
+                    Value::null(),
+                ],
+            ],
+        );
+
+        assert_eq!(
+            result.to_csv(),
+            "id,name,score,avatar\n\
+             1,\"Kit, \"\"the\"\" cat\",0.30000000000000004,AQID\n\
+             2,\"line\nbreak\",,\n"
+        );
+    }
+
+    #[test]
+    fn to_csv_fits_ragged_rows_to_header() {
+        let result = QueryResult::fixture(
+            &["a", "b"],
+            vec![
+                vec![Value::integer(1)],
+                vec![Value::integer(2), Value::integer(3), Value::integer(4)],
+                vec![Value::integer(5), Value::integer(6)],
+            ],
+        );
+
+        assert_eq!(result.to_csv(), "a,b\n1,\n2,3\n5,6\n");
+    }
+}
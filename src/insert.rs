@@ -0,0 +1,368 @@
+use crate::{
+    sql, BunnyDbClient, BunnyDbError, ExecResult, Result, Statement, StatementOutcome, Value,
+};
+
+impl BunnyDbClient {
+    /// Inserts `rows` into `table` using multi-row `INSERT` statements.
+    ///
+    /// Rows are grouped into statements of at most `chunk_size` rows each,
+    /// e.g. `INSERT INTO t (a, b) VALUES (?, ?), (?, ?)`, and all chunks are
+    /// sent in one [`BunnyDbClient::execute_batch_atomic`] call, so either
+    /// every row is inserted or none is. Returns the total affected row count.
+    ///
+    /// Every row must contain exactly `columns.len()` values. Table and column
+    /// names are validated and quoted as by [`crate::quote_identifier`]; the
+    /// table may be schema-qualified, columns may not. Keep `chunk_size * columns.len()`
+    /// below SQLite's bound-parameter limit (32766 on current versions).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run(db: bunnydb_http::BunnyDbClient) -> bunnydb_http::Result<()> {
+    /// use bunnydb_http::Value;
+    ///
+    /// let rows = vec![
+    ///     vec![Value::integer(1), Value::text("Kit")],
+    ///     vec![Value::integer(2), Value::text("Lane")],
+    /// ];
+    /// let inserted = db.insert_many("users", &["id", "name"], rows, 500).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn insert_many<I>(
+        &self,
+        table: &str,
+        columns: &[&str],
+        rows: I,
+        chunk_size: usize,
+    ) -> Result<u64>
+    where
+        I: IntoIterator<Item = Vec<Value>>,
+    {
+        let statements = build_insert_chunks(table, columns, rows, chunk_size)?;
+        if statements.is_empty() {
+            return Ok(0);
+        }
+
+        let outcomes = self.execute_batch_atomic(statements).await?;
+        Ok(outcomes
+            .iter()
+            .map(|outcome| match outcome {
+                StatementOutcome::Exec(exec) => exec.affected_row_count,
+                _ => 0,
+            })
+            .sum())
+    }
+
+    /// Inserts one row into `table`, updating the existing row instead when
+    /// it conflicts on `conflict_columns`.
+    ///
+    /// Generates `INSERT INTO t (..) VALUES (..) ON CONFLICT (..) DO UPDATE
+    /// SET col = excluded.col` for every column not in `conflict_columns`
+    /// (or `DO NOTHING` when there is none), binding all values
+    /// positionally. `conflict_columns` must name a `UNIQUE` or primary key
+    /// constraint and be a subset of the columns in `values`. Table and
+    /// column names are validated and quoted as by
+    /// [`crate::quote_identifier`]; the table may be schema-qualified,
+    /// columns may not.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run(db: bunnydb_http::BunnyDbClient) -> bunnydb_http::Result<()> {
+    /// use bunnydb_http::Value;
+    ///
+    /// db.upsert(
+    ///     "users",
+    ///     &["email"],
+    ///     &[("email", Value::text("kit@example.com")), ("name", Value::text("Kit"))],
+    /// )
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn upsert(
+        &self,
+        table: &str,
+        conflict_columns: &[&str],
+        values: &[(&str, Value)],
+    ) -> Result<ExecResult> {
+        let statement = build_upsert(table, conflict_columns, values)?;
+        self.execute(&statement.sql, statement.params).await
+    }
+}
+
+fn build_upsert(
+    table: &str,
+    conflict_columns: &[&str],
+    values: &[(&str, Value)],
+) -> Result<Statement> {
+    if values.is_empty() {
+        return Err(BunnyDbError::Decode(
+            "upsert requires at least one column".to_owned(),
+        ));
+    }
+    if conflict_columns.is_empty() {
+        return Err(BunnyDbError::Decode(
+            "upsert requires at least one conflict column".to_owned(),
+        ));
+    }
+    let columns: Vec<&str> = values.iter().map(|(column, _)| *column).collect();
+    let table_sql = quote_table(table)?;
+    let quoted = quote_columns(&columns)?;
+    if let Some((index, column)) = columns
+        .iter()
+        .enumerate()
+        .find(|(index, column)| columns[..*index].contains(column))
+    {
+        return Err(BunnyDbError::Decode(format!(
+            "column '{column}' appears more than once in upsert (index {index})"
+        )));
+    }
+    if let Some(missing) = conflict_columns
+        .iter()
+        .find(|column| !columns.contains(column))
+    {
+        return Err(BunnyDbError::Decode(format!(
+            "conflict column '{missing}' is not among the upserted columns"
+        )));
+    }
+
+    let placeholders = vec!["?"; columns.len()].join(", ");
+    let targets = quote_columns(conflict_columns)?;
+    let updates: Vec<String> = columns
+        .iter()
+        .zip(&quoted)
+        .filter(|(column, _)| !conflict_columns.contains(column))
+        .map(|(_, column)| format!("{column} = excluded.{column}"))
+        .collect();
+    let action = if updates.is_empty() {
+        "DO NOTHING".to_owned()
+    } else {
+        format!("DO UPDATE SET {}", updates.join(", "))
+    };
+    let sql = format!(
+        "INSERT INTO {table_sql} ({}) VALUES ({placeholders}) ON CONFLICT ({}) {action}",
+        quoted.join(", "),
+        targets.join(", ")
+    );
+    let params: Vec<Value> = values.iter().map(|(_, value)| value.clone()).collect();
+    Ok(Statement::execute(sql, params))
+}
+
+fn build_insert_chunks<I>(
+    table: &str,
+    columns: &[&str],
+    rows: I,
+    chunk_size: usize,
+) -> Result<Vec<Statement>>
+where
+    I: IntoIterator<Item = Vec<Value>>,
+{
+    if chunk_size == 0 {
+        return Err(BunnyDbError::Decode(
+            "chunk size must be greater than zero".to_owned(),
+        ));
+    }
+    if columns.is_empty() {
+        return Err(BunnyDbError::Decode(
+            "insert requires at least one column".to_owned(),
+        ));
+    }
+    let table_sql = quote_table(table)?;
+    let quoted = quote_columns(columns)?;
+
+    let row_placeholder = format!("({})", vec!["?"; columns.len()].join(", "));
+    let prefix = format!("INSERT INTO {table_sql} ({}) VALUES ", quoted.join(", "));
+
+    let mut statements = Vec::new();
+    let mut values = Vec::new();
+    let mut rows_in_chunk = 0usize;
+
+    for (index, row) in rows.into_iter().enumerate() {
+        if row.len() != columns.len() {
+            return Err(BunnyDbError::Decode(format!(
+                "row {index} has {} values, expected {}",
+                row.len(),
+                columns.len()
+            )));
+        }
+        values.extend(row);
+        rows_in_chunk += 1;
+
+        if rows_in_chunk == chunk_size {
+            statements.push(chunk_statement(
+                &prefix,
+                &row_placeholder,
+                rows_in_chunk,
+                std::mem::take(&mut values),
+            ));
+            rows_in_chunk = 0;
+        }
+    }
+    if rows_in_chunk > 0 {
+        statements.push(chunk_statement(
+            &prefix,
+            &row_placeholder,
+            rows_in_chunk,
+            values,
+        ));
+    }
+
+    Ok(statements)
+}
+
+/// Quotes a table name, optionally qualified with a schema (`main.users`).
+fn quote_table(table: &str) -> Result<String> {
+    let parts = table
+        .split('.')
+        .map(sql::quote_identifier)
+        .collect::<Result<Vec<_>>>()?;
+    if parts.len() > 2 {
+        return Err(BunnyDbError::Decode(format!(
+            "invalid table name `{table}`: expected `table` or `schema.table`"
+        )));
+    }
+    Ok(parts.join("."))
+}
+
+/// Quotes unqualified column names; `t.col` is rejected.
+fn quote_columns(columns: &[&str]) -> Result<Vec<String>> {
+    columns
+        .iter()
+        .map(|column| sql::quote_identifier(column))
+        .collect()
+}
+
+fn chunk_statement(
+    prefix: &str,
+    row_placeholder: &str,
+    rows: usize,
+    values: Vec<Value>,
+) -> Statement {
+    let placeholders = vec![row_placeholder; rows].join(", ");
+    Statement::execute(format!("{prefix}{placeholders}"), values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_insert_chunks, build_upsert};
+    use crate::{BunnyDbError, Params, Value};
+
+    fn row(id: i64) -> Vec<Value> {
+        vec![Value::integer(id), Value::text(format!("user-{id}"))]
+    }
+
+    #[test]
+    fn chunks_rows_with_uneven_final_chunk() {
+        let statements =
+            build_insert_chunks("users", &["id", "name"], (1..=5).map(row), 2).expect("must build");
+
+        assert_eq!(statements.len(), 3);
+        assert_eq!(
+            statements[0].sql,
+            "INSERT INTO \"users\" (\"id\", \"name\") VALUES (?, ?), (?, ?)"
+        );
+        assert_eq!(
+            statements[2].sql,
+            "INSERT INTO \"users\" (\"id\", \"name\") VALUES (?, ?)"
+        );
+        assert_eq!(statements[2].params, Params::positional(row(5)));
+    }
+
+    #[test]
+    fn rejects_row_with_wrong_value_count() {
+        let rows = vec![row(1), vec![Value::integer(2)]];
+        let err = build_insert_chunks("users", &["id", "name"], rows, 10).expect_err("must fail");
+        match err {
+            BunnyDbError::Decode(message) => assert!(message.contains("row 1")),
+            _ => panic!("expected decode error"),
+        }
+    }
+
+    #[test]
+    fn upsert_updates_non_conflict_columns() {
+        let statement = build_upsert(
+            "users",
+            &["email"],
+            &[
+                ("email", Value::text("kit@example.com")),
+                ("name", Value::text("Kit")),
+                ("age", Value::integer(30)),
+            ],
+        )
+        .expect("must build");
+
+        assert_eq!(
+            statement.sql,
+            "INSERT INTO \"users\" (\"email\", \"name\", \"age\") VALUES (?, ?, ?) \
+             ON CONFLICT (\"email\") DO UPDATE SET \"name\" = excluded.\"name\", \
+             \"age\" = excluded.\"age\""
+        );
+        assert_eq!(
+            statement.params,
+            Params::positional([
+                Value::text("kit@example.com"),
+                Value::text("Kit"),
+                Value::integer(30),
+            ])
+        );
+        assert!(!statement.want_rows);
+    }
+
+    #[test]
+    fn upsert_of_only_conflict_columns_does_nothing_on_conflict() {
+        let statement =
+            build_upsert("tags", &["name"], &[("name", Value::text("rust"))]).expect("must build");
+        assert_eq!(
+            statement.sql,
+            "INSERT INTO \"tags\" (\"name\") VALUES (?) ON CONFLICT (\"name\") DO NOTHING"
+        );
+    }
+
+    #[test]
+    fn upsert_rejects_invalid_column_sets() {
+        let values = [("id", Value::integer(1)), ("name", Value::text("Kit"))];
+        for (conflict, expected) in [
+            (&["email"][..], "conflict column 'email'"),
+            (&[][..], "at least one conflict column"),
+        ] {
+            match build_upsert("users", conflict, &values).expect_err("must fail") {
+                BunnyDbError::Decode(message) => assert!(message.contains(expected), "{message}"),
+                other => panic!("expected decode error, got {other:?}"),
+            }
+        }
+
+        let duplicated = [("id", Value::integer(1)), ("id", Value::integer(2))];
+        assert!(build_upsert("users", &["id"], &duplicated).is_err());
+        assert!(build_upsert("users; --", &["id"], &values).is_err());
+        let qualified = [("u.id", Value::integer(1))];
+        assert!(build_upsert("users", &["u.id"], &qualified).is_err());
+    }
+
+    #[test]
+    fn keyword_and_qualified_names_are_quoted() {
+        let statement = build_upsert(
+            "main.orders",
+            &["order"],
+            &[("order", Value::integer(1)), ("group", Value::text("a"))],
+        )
+        .expect("must build");
+        assert_eq!(
+            statement.sql,
+            "INSERT INTO \"main\".\"orders\" (\"order\", \"group\") VALUES (?, ?) \
+             ON CONFLICT (\"order\") DO UPDATE SET \"group\" = excluded.\"group\""
+        );
+
+        let statements =
+            build_insert_chunks("orders", &["order"], vec![vec![Value::integer(1)]], 10)
+                .expect("must build");
+        assert_eq!(
+            statements[0].sql,
+            "INSERT INTO \"orders\" (\"order\") VALUES (?)"
+        );
+        assert!(
+            build_insert_chunks("orders", &["o.id"], vec![vec![Value::integer(1)]], 10).is_err()
+        );
+    }
+}
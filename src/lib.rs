@@ -38,33 +38,72 @@
 //!     bunnydb_http::Params::named([("name", bunnydb_http::Value::text("Kit"))]),
 //! ).await?;
 //!
-//! println!("rows={}", result.rows.len());
+//! println!("rows={}", result.row_count());
 //! # Ok(())
 //! # }
 //! ```
 
+mod backoff;
+mod breaker;
+#[cfg(not(target_arch = "wasm32"))]
+mod cancel;
 mod client;
+mod clock;
 mod decode;
+mod describe;
 mod error;
+mod explain;
+#[cfg(feature = "csv")]
+mod export;
+mod insert;
 mod options;
+mod pager;
 mod params;
+mod pragma;
+mod prepared;
+#[cfg(all(feature = "request-log", not(target_arch = "wasm32")))]
+mod request_log;
+mod session;
+mod sql;
+mod transaction;
 mod types;
 mod value;
 mod wire;
 
 #[cfg(feature = "baton-experimental")]
 pub mod baton;
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub mod blocking;
 #[cfg(feature = "raw-mode")]
 pub mod raw;
 #[cfg(feature = "row-map")]
 pub mod row_map;
 
-pub use client::{db_id_to_pipeline_url, BunnyDbClient};
+pub use backoff::{BackoffStrategy, Constant, Exponential, Linear};
+pub use client::{
+    db_id_to_pipeline_url, db_id_to_pipeline_url_with_path, normalize_pipeline_url, redact_url,
+    BunnyDbClient, DEFAULT_PIPELINE_PATH,
+};
 pub use error::BunnyDbError;
-pub use options::ClientOptions;
-pub use params::{Params, Statement};
-pub use types::{Col, ExecResult, QueryResult, StatementOutcome};
-pub use value::Value;
+pub use explain::QueryPlanStep;
+pub use options::{
+    ClientOptions, DecodeOptions, NonFiniteFloatPolicy, RequestCallback, RequestInspector,
+    RequestMetrics, RequestSize,
+};
+pub use pager::{KeysetPage, QueryPager, CURSOR_TOKEN};
+pub use params::{Params, ParamsBuilder, Statement};
+pub use prepared::{BatchItem, PreparedStatement};
+pub use sql::{expand_in_placeholders, quote_identifier, split_sql};
+pub use transaction::{Savepoint, Transaction};
+pub use types::{
+    batch_telemetry, BatchSummary, BatchTelemetry, Col, ExecResult, QueryResult, StatementOutcome,
+};
+pub use value::{Base64Mode, Value};
+
+/// Re-exported for [`BunnyDbClient::query_cancellable`] and
+/// [`BunnyDbClient::execute_cancellable`].
+#[cfg(not(target_arch = "wasm32"))]
+pub use tokio_util::sync::CancellationToken;
 
 /// Crate-wide result type.
 pub type Result<T> = std::result::Result<T, BunnyDbError>;
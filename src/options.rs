@@ -1,12 +1,105 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{BackoffStrategy, Base64Mode};
+
+/// Callback invoked after every HTTP attempt; see [`ClientOptions::on_request`].
+pub type RequestCallback = Arc<dyn Fn(&RequestMetrics) + Send + Sync>;
+
+/// Callback receiving the serialized request JSON; see
+/// `BunnyDbClient::with_request_inspector`.
+pub type RequestInspector = Arc<dyn Fn(&str) + Send + Sync>;
+
 /// Configures HTTP timeout and retry behavior.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone)]
 pub struct ClientOptions {
-    /// Per-request timeout in milliseconds.
+    /// Per-attempt timeout in milliseconds, covering connect, headers and
+    /// reading the full response body.
     pub timeout_ms: u64,
     /// Maximum number of retries after the initial attempt.
     pub max_retries: usize,
-    /// Base retry backoff in milliseconds (exponential strategy).
+    /// Base retry backoff in milliseconds for the default
+    /// [`crate::Exponential`] strategy; ignored when `backoff` is set.
     pub retry_backoff_ms: u64,
+    /// Custom retry delay policy, e.g. [`crate::Linear`],
+    /// [`crate::Constant`] or a [`BackoffStrategy`] of your own. `None`
+    /// doubles `retry_backoff_ms` after every attempt.
+    pub backoff: Option<Arc<dyn BackoffStrategy>>,
+    /// Gzip request bodies and advertise gzip responses.
+    ///
+    /// Ignored on WASM, where the fetch layer handles encoding.
+    pub compression: bool,
+    /// Called after each completed HTTP attempt, successful or not.
+    ///
+    /// Useful for exporting request counts, latencies and retries without
+    /// the `tracing` feature.
+    pub on_request: Option<RequestCallback>,
+    /// Consecutive failed requests (transport errors or retryable HTTP
+    /// statuses, after retries) that open the circuit breaker. `0` disables
+    /// the breaker.
+    pub breaker_threshold: usize,
+    /// How long an open breaker rejects requests before admitting a probe.
+    pub breaker_cooldown_ms: u64,
+    /// Upper bound on the serialized JSON request body (before compression).
+    /// Larger requests fail with `BunnyDbError::RequestTooLarge` without
+    /// being sent. `None` means unlimited.
+    pub max_request_bytes: Option<usize>,
+    /// Overall time budget in milliseconds for a request including all
+    /// retries and backoff.
+    ///
+    /// Retrying stops (returning the last error) once the next backoff would
+    /// cross the deadline, and each attempt's timeout is clamped to the
+    /// remaining budget. `None` means only `timeout_ms` and `max_retries`
+    /// apply.
+    pub total_deadline_ms: Option<u64>,
+    /// How result cells that cannot be represented exactly are decoded.
+    pub decode: DecodeOptions,
+    /// What happens to a NaN or infinite [`crate::Value::Float`] parameter,
+    /// which SQL cannot store as a number.
+    pub non_finite_float: NonFiniteFloatPolicy,
+    /// Base64 mode of [`crate::Value::BlobBase64`] payloads on the client
+    /// side. Blob parameters are converted from this mode to the wire's
+    /// standard base64, and result blobs back into it; a parameter that is
+    /// not valid in this mode fails with `BunnyDbError::Decode`.
+    pub base64_mode: Base64Mode,
+    /// Validate the pipeline URL when options are applied with
+    /// `BunnyDbClient::try_with_options`, failing early on a malformed URL
+    /// or a path other than `/v<N>/pipeline` (e.g. `/v2/pipeline`).
+    ///
+    /// Off by default so custom endpoints (proxies, test servers) work.
+    pub validate_on_build: bool,
+    /// Check before sending that the placeholders in the SQL match the
+    /// [`crate::Params`] variant, failing with
+    /// `BunnyDbError::ParamStyleMismatch` when named parameters are given
+    /// for SQL with only `?` placeholders, or positional ones for SQL with
+    /// only `:name`/`@name`/`$name` placeholders.
+    ///
+    /// Off by default: the check scans SQL text and is not a parser, so
+    /// unusual SQL can be misjudged.
+    pub check_param_style: bool,
+    /// Response headers to capture into
+    /// [`RequestMetrics::response_headers`], matched case-insensitively,
+    /// e.g. `x-ratelimit-remaining` or `x-request-id`.
+    ///
+    /// Empty by default, so nothing is captured. `Authorization` and
+    /// `Set-Cookie` are never captured even if listed.
+    pub capture_headers: Vec<String>,
+    /// Largest number of statements sent in one pipeline request, for
+    /// endpoints that cap it. `None` (the default) or `Some(0)` means no
+    /// limit.
+    ///
+    /// Each statement counts once, and so does the `store_sql` step
+    /// `batch_items` sends before a prepared statement's first use in a
+    /// request. Larger `batch`, `batch_lenient`, `batch_indexed`,
+    /// `batch_strict` and `batch_items` calls are split into consecutive
+    /// requests with outcomes concatenated in order and `request_index`
+    /// counted across the whole batch; a failed request stops the call, with
+    /// earlier requests already applied. `execute_batch_atomic` (and helpers
+    /// built on it) is never split and fails with `BunnyDbError::Config`
+    /// above the limit.
+    pub max_statements_per_request: Option<usize>,
 }
 
 impl Default for ClientOptions {
@@ -15,6 +108,313 @@ impl Default for ClientOptions {
             timeout_ms: 10_000,
             max_retries: 0,
             retry_backoff_ms: 250,
+            compression: false,
+            on_request: None,
+            backoff: None,
+            breaker_threshold: 0,
+            breaker_cooldown_ms: 30_000,
+            max_request_bytes: None,
+            total_deadline_ms: None,
+            decode: DecodeOptions::default(),
+            non_finite_float: NonFiniteFloatPolicy::default(),
+            base64_mode: Base64Mode::default(),
+            validate_on_build: false,
+            check_param_style: false,
+            capture_headers: Vec::new(),
+            max_statements_per_request: None,
+        }
+    }
+}
+
+impl ClientOptions {
+    /// Fast-fail settings for edge functions: 3 s timeout, no retries.
+    ///
+    /// Matches the WASM default of failing fast rather than holding an edge
+    /// request open.
+    pub fn edge() -> Self {
+        Self {
+            timeout_ms: 3_000,
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Settings for user-facing requests: 5 s timeout, 2 quick retries
+    /// starting at 100 ms.
+    pub fn interactive() -> Self {
+        Self {
+            timeout_ms: 5_000,
+            max_retries: 2,
+            retry_backoff_ms: 100,
+            ..Self::default()
+        }
+    }
+
+    /// Settings for background jobs that should ride out transient
+    /// failures: 30 s timeout, 5 retries starting at 500 ms.
+    pub fn resilient() -> Self {
+        Self {
+            timeout_ms: 30_000,
+            max_retries: 5,
+            retry_backoff_ms: 500,
+            ..Self::default()
+        }
+    }
+
+    /// Reads timeout and retry settings from environment variables, keeping
+    /// the defaults for anything unset:
+    ///
+    /// - `BUNNYDB_TIMEOUT_MS` → [`ClientOptions::timeout_ms`]
+    /// - `BUNNYDB_MAX_RETRIES` → [`ClientOptions::max_retries`]
+    /// - `BUNNYDB_RETRY_BACKOFF_MS` → [`ClientOptions::retry_backoff_ms`]
+    ///
+    /// Empty values count as unset. Returns an error naming the variable
+    /// when a value is not a non-negative integer.
+    ///
+    /// **Not available on `wasm32` targets.**
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_env() -> Result<Self, String> {
+        let defaults = Self::default();
+        Ok(Self {
+            timeout_ms: env_number("BUNNYDB_TIMEOUT_MS", defaults.timeout_ms)?,
+            max_retries: env_number("BUNNYDB_MAX_RETRIES", defaults.max_retries)?,
+            retry_backoff_ms: env_number("BUNNYDB_RETRY_BACKOFF_MS", defaults.retry_backoff_ms)?,
+            ..defaults
+        })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn env_number<T>(name: &str, default: T) -> Result<T, String>
+where
+    T: std::str::FromStr,
+    T::Err: fmt::Display,
+{
+    match std::env::var(name) {
+        Ok(value) if !value.trim().is_empty() => value
+            .trim()
+            .parse()
+            .map_err(|err| format!("invalid {name} value '{value}': {err}")),
+        Ok(_) | Err(std::env::VarError::NotPresent) => Ok(default),
+        Err(std::env::VarError::NotUnicode(_)) => Err(format!("{name} is not valid UTF-8")),
+    }
+}
+
+impl fmt::Debug for ClientOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientOptions")
+            .field("timeout_ms", &self.timeout_ms)
+            .field("max_retries", &self.max_retries)
+            .field("retry_backoff_ms", &self.retry_backoff_ms)
+            .field("compression", &self.compression)
+            .field("breaker_threshold", &self.breaker_threshold)
+            .field("breaker_cooldown_ms", &self.breaker_cooldown_ms)
+            .field("max_request_bytes", &self.max_request_bytes)
+            .field("total_deadline_ms", &self.total_deadline_ms)
+            .field("decode", &self.decode)
+            .field("non_finite_float", &self.non_finite_float)
+            .field("base64_mode", &self.base64_mode)
+            .field("validate_on_build", &self.validate_on_build)
+            .field("check_param_style", &self.check_param_style)
+            .field("capture_headers", &self.capture_headers)
+            .field(
+                "max_statements_per_request",
+                &self.max_statements_per_request,
+            )
+            .field(
+                "on_request",
+                &self.on_request.as_ref().map(|_| "<callback>"),
+            )
+            .field("backoff", &self.backoff.as_ref().map(|_| "<strategy>"))
+            .finish()
+    }
+}
+
+/// Two option sets are equal when their settings match and they share the
+/// same `on_request` callback and `backoff` strategy instances (or both have
+/// none).
+impl PartialEq for ClientOptions {
+    fn eq(&self, other: &Self) -> bool {
+        self.timeout_ms == other.timeout_ms
+            && self.max_retries == other.max_retries
+            && self.retry_backoff_ms == other.retry_backoff_ms
+            && self.compression == other.compression
+            && self.breaker_threshold == other.breaker_threshold
+            && self.breaker_cooldown_ms == other.breaker_cooldown_ms
+            && self.max_request_bytes == other.max_request_bytes
+            && self.total_deadline_ms == other.total_deadline_ms
+            && self.decode == other.decode
+            && self.non_finite_float == other.non_finite_float
+            && self.base64_mode == other.base64_mode
+            && self.validate_on_build == other.validate_on_build
+            && self.check_param_style == other.check_param_style
+            && self.capture_headers == other.capture_headers
+            && self.max_statements_per_request == other.max_statements_per_request
+            && match (&self.on_request, &other.on_request) {
+                (Some(left), Some(right)) => Arc::ptr_eq(left, right),
+                (None, None) => true,
+                _ => false,
+            }
+            && match (&self.backoff, &other.backoff) {
+                (Some(left), Some(right)) => Arc::ptr_eq(left, right),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+}
+
+impl Eq for ClientOptions {}
+
+/// Controls decoding of result cells; see [`ClientOptions::decode`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DecodeOptions {
+    /// Decode out-of-range integers and non-finite or unparsable floats as
+    /// [`crate::Value::Text`] holding the raw wire string, instead of failing
+    /// the whole result with `BunnyDbError::Decode`.
+    ///
+    /// Defaults to `false` (strict).
+    pub lenient: bool,
+}
+
+impl DecodeOptions {
+    /// Strict decoding: any unrepresentable cell is an error.
+    pub fn strict() -> Self {
+        Self { lenient: false }
+    }
+
+    /// Lenient decoding: unrepresentable numbers fall back to text.
+    pub fn lenient() -> Self {
+        Self { lenient: true }
+    }
+}
+
+/// Handling of NaN and infinite float parameters; see
+/// [`ClientOptions::non_finite_float`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NonFiniteFloatPolicy {
+    /// Fail the request with `BunnyDbError::Decode` before sending it.
+    #[default]
+    Error,
+    /// Bind `NULL` instead.
+    Null,
+    /// Bind the value as text: `"NaN"`, `"inf"` or `"-inf"`.
+    Text,
+}
+
+/// Metrics for a single HTTP attempt against the pipeline endpoint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequestMetrics {
+    /// Zero-based attempt number (`0` is the initial request).
+    pub attempt: usize,
+    /// HTTP status, or `None` when the attempt failed before a response.
+    pub status: Option<u16>,
+    /// Wall-clock duration of the attempt, including reading the body.
+    pub duration: Duration,
+    /// Whether another attempt follows this one.
+    pub retried: bool,
+    /// Size of the request body in bytes (after compression).
+    pub bytes_sent: usize,
+    /// Size of the response body text in bytes (after decompression), or
+    /// `0` when no body was read.
+    pub bytes_received: usize,
+    /// Bytes sent and received by all attempts of this call so far,
+    /// including this one. On the last attempt (`retried == false`) this is
+    /// the call's total traffic.
+    pub total: RequestSize,
+    /// Response headers listed in [`ClientOptions::capture_headers`], keyed
+    /// by lowercase name. `None` when no headers are configured or the
+    /// attempt got no response; listed headers absent from the response are
+    /// left out.
+    pub response_headers: Option<HashMap<String, String>>,
+}
+
+/// Request and response byte counts, as reported in
+/// [`RequestMetrics::total`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RequestSize {
+    /// Request body bytes, after compression.
+    pub request_bytes: usize,
+    /// Response body bytes, after decompression.
+    pub response_bytes: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The only test touching these variables, so parallel tests do not
+    /// race on them.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn from_env_reads_overrides_and_rejects_garbage() {
+        const VARS: [&str; 3] = [
+            "BUNNYDB_TIMEOUT_MS",
+            "BUNNYDB_MAX_RETRIES",
+            "BUNNYDB_RETRY_BACKOFF_MS",
+        ];
+        for name in VARS {
+            std::env::remove_var(name);
+        }
+        assert_eq!(ClientOptions::from_env(), Ok(ClientOptions::default()));
+
+        std::env::set_var("BUNNYDB_TIMEOUT_MS", "1500");
+        std::env::set_var("BUNNYDB_MAX_RETRIES", " 3 ");
+        std::env::set_var("BUNNYDB_RETRY_BACKOFF_MS", "");
+        let options = ClientOptions::from_env().expect("valid values must parse");
+        assert_eq!(options.timeout_ms, 1_500);
+        assert_eq!(options.max_retries, 3);
+        assert_eq!(options.retry_backoff_ms, 250);
+
+        std::env::set_var("BUNNYDB_MAX_RETRIES", "-1");
+        let err = ClientOptions::from_env().expect_err("negative retries must fail");
+        assert!(err.contains("BUNNYDB_MAX_RETRIES"), "{err}");
+
+        for name in VARS {
+            std::env::remove_var(name);
+        }
+    }
+
+    #[test]
+    fn presets_have_expected_values() {
+        let presets = [
+            (ClientOptions::edge(), 3_000, 0, 250),
+            (ClientOptions::interactive(), 5_000, 2, 100),
+            (ClientOptions::resilient(), 30_000, 5, 500),
+        ];
+        for (options, timeout_ms, max_retries, retry_backoff_ms) in presets {
+            assert_eq!(options.timeout_ms, timeout_ms);
+            assert_eq!(options.max_retries, max_retries);
+            assert_eq!(options.retry_backoff_ms, retry_backoff_ms);
+            assert_eq!(
+                ClientOptions {
+                    timeout_ms: 10_000,
+                    max_retries: 0,
+                    retry_backoff_ms: 250,
+                    ..options
+                },
+                ClientOptions::default(),
+                "presets only change timeout and retry settings"
+            );
         }
     }
+
+    #[test]
+    fn equality_compares_callback_identity() {
+        let callback: RequestCallback = Arc::new(|_| {});
+        let with_callback = ClientOptions {
+            on_request: Some(callback.clone()),
+            ..ClientOptions::default()
+        };
+
+        assert_eq!(ClientOptions::default(), ClientOptions::default());
+        assert_eq!(with_callback, with_callback.clone());
+        assert_ne!(with_callback, ClientOptions::default());
+        assert_ne!(
+            with_callback,
+            ClientOptions {
+                on_request: Some(Arc::new(|_| {})),
+                ..ClientOptions::default()
+            }
+        );
+    }
 }
@@ -0,0 +1,685 @@
+use futures_core::Stream;
+use futures_util::stream;
+
+use crate::{sql, BunnyDbClient, BunnyDbError, Params, QueryResult, Result, Value};
+
+const LIMIT_PARAM: &str = "bunnydb_page_limit";
+const OFFSET_PARAM: &str = "bunnydb_page_offset";
+const CURSOR_PARAM: &str = "bunnydb_cursor";
+
+/// Marker that [`BunnyDbClient::query_keyset`] replaces with the cursor condition.
+pub const CURSOR_TOKEN: &str = "{cursor}";
+
+/// One page returned by [`BunnyDbClient::query_keyset`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeysetPage {
+    /// Rows of this page, ordered by the cursor column.
+    pub result: QueryResult,
+    /// Cursor value to pass as `last_value` for the next page.
+    ///
+    /// `None` when this page was shorter than the page size, meaning there
+    /// are no further rows.
+    pub next_cursor: Option<Value>,
+}
+
+/// Offset-based pager returned by [`BunnyDbClient::query_paged`].
+///
+/// Each call to [`QueryPager::next_page`] issues one pipeline request with
+/// `LIMIT ? OFFSET ?` appended to the original SQL.
+#[derive(Debug)]
+pub struct QueryPager<'a> {
+    client: &'a BunnyDbClient,
+    sql: String,
+    params: Params,
+    page_size: u64,
+    offset: u64,
+    done: bool,
+}
+
+impl QueryPager<'_> {
+    /// Fetches the next page of rows.
+    ///
+    /// Returns `Ok(None)` once the result set is exhausted. A page shorter
+    /// than the configured page size is returned as the final page.
+    pub async fn next_page(&mut self) -> Result<Option<QueryResult>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let (sql, params) = self.page_statement();
+        let page = self.client.query(&sql, params).await?;
+        let len = page.rows.len() as u64;
+
+        self.offset += len;
+        if len < self.page_size {
+            self.done = true;
+        }
+        if len == 0 {
+            return Ok(None);
+        }
+        Ok(Some(page))
+    }
+
+    /// Number of rows fetched so far.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    fn page_statement(&self) -> (String, Params) {
+        let limit = Value::Integer(i64::try_from(self.page_size).unwrap_or(i64::MAX));
+        let offset = Value::Integer(i64::try_from(self.offset).unwrap_or(i64::MAX));
+
+        match &self.params {
+            Params::Positional(values) => {
+                let mut values = values.clone();
+                values.push(limit);
+                values.push(offset);
+                (
+                    sql::append_clause(&self.sql, "LIMIT ? OFFSET ?"),
+                    Params::Positional(values),
+                )
+            }
+            Params::Named(values) => {
+                let mut values = values.clone();
+                values.push((LIMIT_PARAM.to_owned(), limit));
+                values.push((OFFSET_PARAM.to_owned(), offset));
+                (
+                    sql::append_clause(
+                        &self.sql,
+                        &format!("LIMIT :{LIMIT_PARAM} OFFSET :{OFFSET_PARAM}"),
+                    ),
+                    Params::Named(values),
+                )
+            }
+            Params::Mixed { positional, named } => {
+                let mut named = named.clone();
+                named.push((LIMIT_PARAM.to_owned(), limit));
+                named.push((OFFSET_PARAM.to_owned(), offset));
+                (
+                    sql::append_clause(
+                        &self.sql,
+                        &format!("LIMIT :{LIMIT_PARAM} OFFSET :{OFFSET_PARAM}"),
+                    ),
+                    Params::Mixed {
+                        positional: positional.clone(),
+                        named,
+                    },
+                )
+            }
+        }
+    }
+}
+
+impl BunnyDbClient {
+    /// Returns a pager that reads a query result in pages of `page_size` rows.
+    ///
+    /// This is offset pagination: each page re-runs the query with
+    /// `LIMIT ? OFFSET ?` appended, so the SQL should include an `ORDER BY`
+    /// on a unique key for page boundaries to be stable. Offsets get slower
+    /// as they grow; prefer keyset pagination for very large tables.
+    ///
+    /// Fails with [`BunnyDbError::Decode`] if `page_size` is zero, the SQL
+    /// holds more than one statement, or it already contains a top-level
+    /// `LIMIT` clause.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run(db: bunnydb_http::BunnyDbClient) -> bunnydb_http::Result<()> {
+    /// let mut pager = db.query_paged("SELECT id, name FROM users ORDER BY id", (), 500)?;
+    /// while let Some(page) = pager.next_page().await? {
+    ///     println!("rows={}", page.row_count());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn query_paged<P: Into<Params>>(
+        &self,
+        sql: &str,
+        params: P,
+        page_size: u64,
+    ) -> Result<QueryPager<'_>> {
+        if page_size == 0 {
+            return Err(BunnyDbError::Decode(
+                "page size must be greater than zero".to_owned(),
+            ));
+        }
+
+        let sql = sql::single_statement(sql)?;
+        if sql::has_top_level_keyword(&sql, "LIMIT") {
+            return Err(BunnyDbError::Decode(
+                "paged query SQL must not contain a LIMIT clause".to_owned(),
+            ));
+        }
+
+        Ok(QueryPager {
+            client: self,
+            sql,
+            params: params.into(),
+            page_size,
+            offset: 0,
+            done: false,
+        })
+    }
+
+    /// Streams the rows of a query one at a time, fetching pages of
+    /// `page_size` rows behind the scenes.
+    ///
+    /// Built on [`BunnyDbClient::query_paged`], so the same rules apply: the
+    /// SQL needs a stable `ORDER BY` and no `LIMIT`. Only one page is held in
+    /// memory at a time. Invalid arguments and failed page requests are
+    /// yielded as an `Err` item, after which the stream ends.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run(db: bunnydb_http::BunnyDbClient) -> bunnydb_http::Result<()> {
+    /// use futures_util::TryStreamExt;
+    ///
+    /// db.query_stream("SELECT id, name FROM users ORDER BY id", (), 500)
+    ///     .try_for_each(|row| async move {
+    ///         println!("{row:?}");
+    ///         Ok(())
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn query_stream<P: Into<Params>>(
+        &self,
+        sql: &str,
+        params: P,
+        page_size: u64,
+    ) -> impl Stream<Item = Result<Vec<Value>>> + '_ {
+        let state = match self.query_paged(sql, params, page_size) {
+            Ok(pager) => RowStreamState::Paging {
+                pager,
+                rows: Vec::new().into_iter(),
+            },
+            Err(err) => RowStreamState::Failed(err),
+        };
+
+        stream::unfold(state, |state| async move {
+            match state {
+                RowStreamState::Paging {
+                    mut pager,
+                    mut rows,
+                } => loop {
+                    if let Some(row) = rows.next() {
+                        return Some((Ok(row), RowStreamState::Paging { pager, rows }));
+                    }
+                    match pager.next_page().await {
+                        Ok(Some(page)) => rows = page.rows.into_iter(),
+                        Ok(None) => return None,
+                        Err(err) => return Some((Err(err), RowStreamState::Done)),
+                    }
+                },
+                RowStreamState::Failed(err) => Some((Err(err), RowStreamState::Done)),
+                RowStreamState::Done => None,
+            }
+        })
+    }
+
+    /// Fetches one page using keyset (cursor) pagination on `cursor_col`.
+    ///
+    /// The template is extended with a `cursor_col > ?` condition and
+    /// `ORDER BY cursor_col LIMIT ?`. The condition is placed, in order of
+    /// preference:
+    ///
+    /// - in place of a [`CURSOR_TOKEN`] (`{cursor}`) marker in the template,
+    /// - combined with an existing top-level `WHERE` as `(...) AND ...`,
+    /// - as a new `WHERE` before any top-level `GROUP BY`/`HAVING`/`WINDOW`.
+    ///
+    /// Pass `None` as `last_value` for the first page, then the returned
+    /// [`KeysetPage::next_cursor`] until it is `None`. `cursor_col` must be
+    /// a plain (optionally table-qualified) column name that is unique and
+    /// present in the select list; the template must be a single statement
+    /// without its own top-level `ORDER BY` or `LIMIT`. With positional
+    /// params the cursor value is inserted among them, so the template must
+    /// use plain `?` rather than numbered `?NNN` placeholders.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run(db: bunnydb_http::BunnyDbClient) -> bunnydb_http::Result<()> {
+    /// let mut cursor = None;
+    /// loop {
+    ///     let page = db
+    ///         .query_keyset("SELECT id, name FROM users", (), "id", cursor, 500)
+    ///         .await?;
+    ///     println!("rows={}", page.result.row_count());
+    ///     match page.next_cursor {
+    ///         Some(next) => cursor = Some(next),
+    ///         None => break,
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query_keyset<P: Into<Params>>(
+        &self,
+        sql_template: &str,
+        params: P,
+        cursor_col: &str,
+        last_value: Option<Value>,
+        page_size: u64,
+    ) -> Result<KeysetPage> {
+        let (sql, params) = build_keyset_statement(
+            sql_template,
+            params.into(),
+            cursor_col,
+            last_value,
+            page_size,
+        )?;
+        let result = self.query(&sql, params).await?;
+
+        if (result.row_count() as u64) < page_size {
+            return Ok(KeysetPage {
+                result,
+                next_cursor: None,
+            });
+        }
+
+        let lookup = cursor_col.rsplit('.').next().unwrap_or(cursor_col);
+        let index = result.column_index(lookup).ok_or_else(|| {
+            BunnyDbError::Decode(format!(
+                "cursor column '{cursor_col}' is not present in the query result"
+            ))
+        })?;
+        let next_cursor = result.rows.last().and_then(|row| row.get(index)).cloned();
+
+        Ok(KeysetPage {
+            result,
+            next_cursor,
+        })
+    }
+}
+
+/// State of [`BunnyDbClient::query_stream`] between rows.
+enum RowStreamState<'a> {
+    Paging {
+        pager: QueryPager<'a>,
+        rows: std::vec::IntoIter<Vec<Value>>,
+    },
+    Failed(BunnyDbError),
+    Done,
+}
+
+fn build_keyset_statement(
+    sql_template: &str,
+    params: Params,
+    cursor_col: &str,
+    last_value: Option<Value>,
+    page_size: u64,
+) -> Result<(String, Params)> {
+    if page_size == 0 {
+        return Err(BunnyDbError::Decode(
+            "page size must be greater than zero".to_owned(),
+        ));
+    }
+    if !sql::is_plain_identifier(cursor_col) {
+        return Err(BunnyDbError::Decode(format!(
+            "invalid cursor column name '{cursor_col}'"
+        )));
+    }
+
+    let template = sql::single_statement(sql_template)?;
+    let template = template.as_str();
+    let words = sql::scan_words(template);
+    let top_level = |keyword: &str| {
+        words
+            .iter()
+            .find(|word| word.depth == 0 && word.is(keyword))
+            .copied()
+    };
+    if top_level("ORDER").is_some() || top_level("LIMIT").is_some() {
+        return Err(BunnyDbError::Decode(
+            "keyset query template must not contain ORDER BY or LIMIT".to_owned(),
+        ));
+    }
+
+    let named = !matches!(params, Params::Positional(_));
+    if !named && sql::has_numbered_placeholder(template) {
+        return Err(BunnyDbError::Decode(
+            "keyset query template with positional params must use plain `?` placeholders"
+                .to_owned(),
+        ));
+    }
+    let condition = if named {
+        format!("{cursor_col} > :{CURSOR_PARAM}")
+    } else {
+        format!("{cursor_col} > ?")
+    };
+
+    let cursor_token = sql::find_outside_literals(template, CURSOR_TOKEN)
+        .first()
+        .copied();
+    let (body, insert_at) = if let Some(token_at) = cursor_token {
+        let replacement = if last_value.is_some() {
+            condition.as_str()
+        } else {
+            "1"
+        };
+        let body = format!(
+            "{}{replacement}{}",
+            &template[..token_at],
+            &template[token_at + CURSOR_TOKEN.len()..]
+        );
+        (body, token_at)
+    } else if last_value.is_none() {
+        (template.to_owned(), template.len())
+    } else {
+        let clause_end = words
+            .iter()
+            .find(|word| {
+                word.depth == 0 && (word.is("GROUP") || word.is("HAVING") || word.is("WINDOW"))
+            })
+            .map_or(template.len(), |word| word.start);
+        let head = &template[..clause_end];
+        let tail = template[clause_end..].trim_start();
+        let tail = if tail.is_empty() {
+            String::new()
+        } else {
+            format!(" {tail}")
+        };
+
+        match top_level("WHERE").filter(|word| word.start < clause_end) {
+            Some(where_word) => {
+                let filter_start = where_word.start + where_word.text.len();
+                let existing = template[filter_start..clause_end].trim();
+                let close = if sql::ends_in_line_comment(existing) {
+                    "\n)"
+                } else {
+                    ")"
+                };
+                let body = format!(
+                    "{} ({existing}{close} AND {condition}{tail}",
+                    &template[..filter_start]
+                );
+                (body, clause_end)
+            }
+            None => (
+                format!(
+                    "{}{tail}",
+                    sql::append_clause(head.trim_end(), &format!("WHERE {condition}"))
+                ),
+                clause_end,
+            ),
+        }
+    };
+
+    let limit = Value::Integer(i64::try_from(page_size).unwrap_or(i64::MAX));
+    let params = match params {
+        Params::Positional(mut values) => {
+            if let Some(last_value) = last_value {
+                let index =
+                    sql::count_positional_placeholders(&template[..insert_at]).min(values.len());
+                values.insert(index, last_value);
+            }
+            values.push(limit);
+            Params::Positional(values)
+        }
+        Params::Named(mut values) => {
+            if let Some(last_value) = last_value {
+                values.push((CURSOR_PARAM.to_owned(), last_value));
+            }
+            values.push((LIMIT_PARAM.to_owned(), limit));
+            Params::Named(values)
+        }
+        Params::Mixed {
+            positional,
+            mut named,
+        } => {
+            if let Some(last_value) = last_value {
+                named.push((CURSOR_PARAM.to_owned(), last_value));
+            }
+            named.push((LIMIT_PARAM.to_owned(), limit));
+            Params::Mixed { positional, named }
+        }
+    };
+
+    let limit_placeholder = if named {
+        format!(":{LIMIT_PARAM}")
+    } else {
+        "?".to_owned()
+    };
+    let sql = sql::append_clause(
+        &body,
+        &format!("ORDER BY {cursor_col} LIMIT {limit_placeholder}"),
+    );
+    Ok((sql, params))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build_keyset_statement;
+    use crate::{BunnyDbClient, BunnyDbError, Params, Value};
+
+    #[test]
+    fn query_paged_rejects_existing_limit() {
+        let db = BunnyDbClient::new("https://db/v2/pipeline", "token");
+        let err = db
+            .query_paged("SELECT * FROM users LIMIT 10;", (), 5)
+            .expect_err("must reject LIMIT");
+        assert!(matches!(err, BunnyDbError::Decode(_)));
+    }
+
+    #[test]
+    fn page_statement_appends_limit_and_offset() {
+        let db = BunnyDbClient::new("https://db/v2/pipeline", "token");
+        let pager = db
+            .query_paged(
+                "SELECT * FROM (SELECT * FROM users LIMIT 100) ORDER BY id;",
+                [Value::integer(1)],
+                25,
+            )
+            .expect("subquery LIMIT is allowed");
+
+        let (sql, params) = pager.page_statement();
+        assert_eq!(
+            sql,
+            "SELECT * FROM (SELECT * FROM users LIMIT 100) ORDER BY id LIMIT ? OFFSET ?"
+        );
+        assert_eq!(
+            params,
+            Params::positional([Value::integer(1), Value::integer(25), Value::integer(0)])
+        );
+    }
+
+    #[test]
+    fn page_statement_survives_trailing_line_comment() {
+        let db = BunnyDbClient::new("https://db/v2/pipeline", "token");
+        let pager = db
+            .query_paged(
+                "SELECT id FROM users WHERE org = :org ORDER BY id -- newest; ",
+                Params::named([("org", Value::integer(1))]),
+                25,
+            )
+            .expect("must build pager");
+
+        let (sql, _) = pager.page_statement();
+        assert_eq!(
+            sql,
+            "SELECT id FROM users WHERE org = :org ORDER BY id -- newest;\n\
+             LIMIT :bunnydb_page_limit OFFSET :bunnydb_page_offset"
+        );
+    }
+
+    #[test]
+    fn paging_drops_comment_after_terminator() {
+        let db = BunnyDbClient::new("https://db/v2/pipeline", "token");
+        let pager = db
+            .query_paged("SELECT * FROM t ORDER BY id; -- all", (), 25)
+            .expect("must build pager");
+        let (sql, _) = pager.page_statement();
+        assert_eq!(sql, "SELECT * FROM t ORDER BY id LIMIT ? OFFSET ?");
+
+        let (sql, _) = build_keyset_statement(
+            "SELECT id FROM t; /* all */",
+            Params::default(),
+            "id",
+            None,
+            10,
+        )
+        .expect("must build");
+        assert_eq!(sql, "SELECT id FROM t ORDER BY id LIMIT ?");
+
+        let err = db
+            .query_paged("SELECT * FROM t ORDER BY id; DELETE FROM t", (), 25)
+            .expect_err("must reject a second statement");
+        assert!(matches!(err, BunnyDbError::Decode(_)));
+    }
+
+    #[test]
+    fn keyset_first_page_has_no_cursor_condition() {
+        let (sql, params) =
+            build_keyset_statement("SELECT id FROM users", Params::default(), "id", None, 10)
+                .expect("must build");
+        assert_eq!(sql, "SELECT id FROM users ORDER BY id LIMIT ?");
+        assert_eq!(params, Params::positional([Value::integer(10)]));
+    }
+
+    #[test]
+    fn keyset_wraps_existing_where_and_orders_params() {
+        let (sql, params) = build_keyset_statement(
+            "SELECT id, org FROM users WHERE org = ? OR org = ? GROUP BY id HAVING COUNT(*) > ?",
+            Params::positional([Value::integer(1), Value::integer(2), Value::integer(0)]),
+            "id",
+            Some(Value::integer(50)),
+            10,
+        )
+        .expect("must build");
+
+        assert_eq!(
+            sql,
+            "SELECT id, org FROM users WHERE (org = ? OR org = ?) AND id > ? \
+             GROUP BY id HAVING COUNT(*) > ? ORDER BY id LIMIT ?"
+        );
+        assert_eq!(
+            params,
+            Params::positional([
+                Value::integer(1),
+                Value::integer(2),
+                Value::integer(50),
+                Value::integer(0),
+                Value::integer(10),
+            ])
+        );
+    }
+
+    #[test]
+    fn keyset_replaces_cursor_token_with_named_params() {
+        let (sql, params) = build_keyset_statement(
+            "SELECT u.id FROM users u JOIN orgs o ON o.id = u.org WHERE {cursor} AND o.name = :org",
+            Params::named([("org", Value::text("acme"))]),
+            "u.id",
+            Some(Value::integer(7)),
+            5,
+        )
+        .expect("must build");
+
+        assert_eq!(
+            sql,
+            "SELECT u.id FROM users u JOIN orgs o ON o.id = u.org \
+             WHERE u.id > :bunnydb_cursor AND o.name = :org ORDER BY u.id LIMIT :bunnydb_page_limit"
+        );
+        match params {
+            Params::Named(values) => assert_eq!(values.len(), 3),
+            _ => panic!("expected named"),
+        }
+    }
+
+    #[test]
+    fn keyset_clauses_survive_line_comments() {
+        let (sql, _) = build_keyset_statement(
+            "SELECT id FROM users -- all users",
+            Params::default(),
+            "id",
+            Some(Value::integer(3)),
+            10,
+        )
+        .expect("must build");
+        assert_eq!(
+            sql,
+            "SELECT id FROM users -- all users\nWHERE id > ? ORDER BY id LIMIT ?"
+        );
+
+        let (sql, _) = build_keyset_statement(
+            "SELECT id FROM users WHERE active = 1 -- only active\nGROUP BY id",
+            Params::default(),
+            "id",
+            Some(Value::integer(3)),
+            10,
+        )
+        .expect("must build");
+        assert_eq!(
+            sql,
+            "SELECT id FROM users WHERE (active = 1 -- only active\n) AND id > ? \
+             GROUP BY id ORDER BY id LIMIT ?"
+        );
+
+        let (sql, _) = build_keyset_statement(
+            "SELECT id FROM users WHERE {cursor} -- paged",
+            Params::default(),
+            "id",
+            None,
+            10,
+        )
+        .expect("must build");
+        assert_eq!(
+            sql,
+            "SELECT id FROM users WHERE 1 -- paged\nORDER BY id LIMIT ?"
+        );
+    }
+
+    #[test]
+    fn keyset_cursor_token_skips_literals_and_comments() {
+        let (sql, params) = build_keyset_statement(
+            "SELECT id FROM notes /* {cursor} */ WHERE body <> '{cursor}' AND {cursor} AND org = ?",
+            Params::Positional(vec![Value::integer(7)]),
+            "id",
+            Some(Value::integer(3)),
+            10,
+        )
+        .expect("must build");
+        assert_eq!(
+            sql,
+            "SELECT id FROM notes /* {cursor} */ WHERE body <> '{cursor}' AND id > ? AND org = ? \
+             ORDER BY id LIMIT ?"
+        );
+        assert_eq!(
+            params,
+            Params::Positional(vec![
+                Value::integer(3),
+                Value::integer(7),
+                Value::integer(10)
+            ])
+        );
+    }
+
+    #[test]
+    fn keyset_rejects_numbered_positional_placeholders() {
+        let err = build_keyset_statement(
+            "SELECT id FROM users WHERE org = ?1 AND {cursor}",
+            Params::Positional(vec![Value::integer(7)]),
+            "id",
+            Some(Value::integer(3)),
+            10,
+        )
+        .expect_err("must reject ?NNN");
+        assert!(matches!(err, BunnyDbError::Decode(_)));
+    }
+
+    #[test]
+    fn keyset_rejects_invalid_cursor_column() {
+        let err = build_keyset_statement(
+            "SELECT id FROM users",
+            Params::default(),
+            "id; DROP TABLE users",
+            None,
+            5,
+        )
+        .expect_err("must reject");
+        assert!(matches!(err, BunnyDbError::Decode(_)));
+    }
+}
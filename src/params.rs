@@ -1,12 +1,43 @@
-use crate::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+
+use serde_json::Value as JsonValue;
+
+use crate::{sql, BunnyDbError, Result, Value};
 
 /// SQL parameter container.
+///
+/// With the `serde` feature, params serialize as `{"type": "positional",
+/// "values": [...]}`, `{"type": "named", "values": [["name", value], ...]}` or
+/// `{"type": "mixed", "values": {"positional": [...], "named": [...]}}`, with
+/// each value in [`Value`]'s format. This format only changes in a major
+/// release, so persisted params stay readable across minor upgrades.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "type", content = "values", rename_all = "snake_case")
+)]
 pub enum Params {
     /// Positional values mapped to `?` placeholders.
     Positional(Vec<Value>),
     /// Named values mapped to `:name` style placeholders.
+    ///
+    /// Values are sent in the order given. Each name may appear once;
+    /// `:id`, `@id`, `$id` and `id` all count as the same name, and a
+    /// repeat fails the request with [`crate::BunnyDbError::Decode`].
     Named(Vec<(String, Value)>),
+    /// Positional and named values bound in the same statement.
+    ///
+    /// SQLite numbers named placeholders as well, so every `?` placeholder
+    /// must appear before the first `:name`/`@name`/`$name` placeholder in
+    /// the SQL text for the positional values to bind to the intended slots.
+    Mixed {
+        /// Values mapped to `?` placeholders, in order.
+        positional: Vec<Value>,
+        /// Values mapped to named placeholders.
+        named: Vec<(String, Value)>,
+    },
 }
 
 impl Params {
@@ -30,6 +61,54 @@ impl Params {
                 .collect(),
         )
     }
+
+    /// Returns a builder for accumulating parameters incrementally.
+    pub fn builder() -> ParamsBuilder {
+        ParamsBuilder::default()
+    }
+
+    /// Number of bound values, positional and named combined.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Positional(values) => values.len(),
+            Self::Named(values) => values.len(),
+            Self::Mixed { positional, named } => positional.len() + named.len(),
+        }
+    }
+
+    /// Returns `true` when no values are bound.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` for [`Params::Positional`], including the empty
+    /// default.
+    pub fn is_positional(&self) -> bool {
+        matches!(self, Self::Positional(_))
+    }
+
+    /// Returns `true` for [`Params::Named`]. [`Params::Mixed`] is neither
+    /// named nor positional.
+    pub fn is_named(&self) -> bool {
+        matches!(self, Self::Named(_))
+    }
+
+    /// Builds parameters combining positional and named values.
+    ///
+    /// See [`Params::Mixed`] for placeholder ordering requirements.
+    pub fn mixed<I, K>(positional: impl Into<Vec<Value>>, named: I) -> Self
+    where
+        I: IntoIterator<Item = (K, Value)>,
+        K: Into<String>,
+    {
+        Self::Mixed {
+            positional: positional.into(),
+            named: named
+                .into_iter()
+                .map(|(name, value)| (name.into(), value))
+                .collect(),
+        }
+    }
 }
 
 impl Default for Params {
@@ -62,12 +141,197 @@ impl From<Vec<(String, Value)>> for Params {
     }
 }
 
+impl Params {
+    /// Parses parameters from JSON text, as accepted from UIs and JS hosts.
+    ///
+    /// Blank input means no parameters; otherwise see
+    /// [`Params::from_json_value`].
+    pub fn from_json(input: &str) -> Result<Self> {
+        let trimmed = input.trim();
+        if trimmed.is_empty() {
+            return Ok(Self::default());
+        }
+        let parsed = serde_json::from_str::<JsonValue>(trimmed)
+            .map_err(|err| BunnyDbError::Decode(format!("invalid params JSON: {err}")))?;
+        Self::from_json_value(parsed)
+    }
+
+    /// Converts a JSON value into parameters.
+    ///
+    /// An array becomes positional parameters, an object named parameters
+    /// and `null` no parameters. Each value may be `null`, a boolean (bound
+    /// as `0`/`1`), a number, a string, or `{"blob_base64": "..."}`; nested
+    /// arrays, other objects and non-finite numbers are rejected with
+    /// [`BunnyDbError::Decode`].
+    pub fn from_json_value(value: JsonValue) -> Result<Self> {
+        match value {
+            JsonValue::Array(items) => {
+                let values = items
+                    .into_iter()
+                    .map(value_from_json)
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(Self::Positional(values))
+            }
+            JsonValue::Object(map) => {
+                let values = map
+                    .into_iter()
+                    .map(|(name, value)| value_from_json(value).map(|value| (name, value)))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(Self::Named(values))
+            }
+            JsonValue::Null => Ok(Self::default()),
+            _ => Err(BunnyDbError::Decode(
+                "params JSON must be either array, object, or null".to_owned(),
+            )),
+        }
+    }
+}
+
+fn value_from_json(value: JsonValue) -> Result<Value> {
+    match value {
+        JsonValue::Null => Ok(Value::Null),
+        JsonValue::Bool(flag) => Ok(Value::Integer(i64::from(flag))),
+        JsonValue::Number(number) => {
+            if let Some(integer) = number.as_i64() {
+                return Ok(Value::Integer(integer));
+            }
+            match number.as_f64() {
+                Some(float) if float.is_finite() => Ok(Value::Float(float)),
+                _ => Err(BunnyDbError::Decode(format!(
+                    "unsupported number '{number}' in params JSON"
+                ))),
+            }
+        }
+        JsonValue::String(text) => Ok(Value::Text(text)),
+        JsonValue::Array(_) => Err(BunnyDbError::Decode(
+            "nested arrays are not supported in parameter values".to_owned(),
+        )),
+        JsonValue::Object(mut map) => match map.remove("blob_base64") {
+            Some(JsonValue::String(blob)) if map.is_empty() => Ok(Value::BlobBase64(blob)),
+            _ => Err(BunnyDbError::Decode(
+                "object parameter values must be {\"blob_base64\": \"...\"}".to_owned(),
+            )),
+        },
+    }
+}
+
+/// Incremental [`Params`] builder, created with [`Params::builder`].
+///
+/// Useful when parameters are added conditionally or in a loop:
+///
+/// ```
+/// use bunnydb_http::{Params, Value};
+///
+/// let name = Some("kit");
+/// let mut builder = Params::builder();
+/// if let Some(name) = name {
+///     builder.bind("name", name);
+/// }
+/// assert_eq!(builder.build(), Params::named([("name", Value::text("kit"))]));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParamsBuilder {
+    positional: Vec<Value>,
+    named: Vec<(String, Value)>,
+}
+
+impl ParamsBuilder {
+    /// Appends a positional value for the next `?` placeholder.
+    pub fn push(&mut self, value: impl Into<Value>) -> &mut Self {
+        self.positional.push(value.into());
+        self
+    }
+
+    /// Binds a value to a named placeholder.
+    pub fn bind(&mut self, name: impl Into<String>, value: impl Into<Value>) -> &mut Self {
+        self.named.push((name.into(), value.into()));
+        self
+    }
+
+    /// Finishes the builder.
+    ///
+    /// Produces [`Params::Positional`] or [`Params::Named`] when only one
+    /// kind of value was added, [`Params::Mixed`] when both were, and empty
+    /// positional parameters when nothing was added.
+    pub fn build(self) -> Params {
+        match (self.positional.is_empty(), self.named.is_empty()) {
+            (_, true) => Params::Positional(self.positional),
+            (true, false) => Params::Named(self.named),
+            (false, false) => Params::Mixed {
+                positional: self.positional,
+                named: self.named,
+            },
+        }
+    }
+}
+
+/// Named parameters from a hash map.
+///
+/// Iteration order of a `HashMap` is unspecified, which does not matter for
+/// named arguments since they bind by name.
+impl<K, S> From<HashMap<K, Value, S>> for Params
+where
+    K: Into<String>,
+    S: BuildHasher,
+{
+    fn from(values: HashMap<K, Value, S>) -> Self {
+        Self::named(values)
+    }
+}
+
+/// Named parameters from an ordered map, bound in key order.
+impl<K: Into<String>> From<BTreeMap<K, Value>> for Params {
+    fn from(values: BTreeMap<K, Value>) -> Self {
+        Self::named(values)
+    }
+}
+
+/// Builds [`Params`] from a list of values or `name => value` pairs.
+///
+/// Values accept anything implementing `Into<Value>`.
+///
+/// ```
+/// use bunnydb_http::{params, Params, Value};
+///
+/// let positional = params![1, "kit", None::<i64>];
+/// assert_eq!(
+///     positional,
+///     Params::positional([Value::integer(1), Value::text("kit"), Value::null()])
+/// );
+///
+/// let named = params! { "name" => "kit", ":age" => 30 };
+/// assert!(matches!(named, Params::Named(_)));
+///
+/// assert_eq!(params![], Params::default());
+/// ```
+#[macro_export]
+macro_rules! params {
+    () => {
+        $crate::Params::default()
+    };
+    ($($name:expr => $value:expr),+ $(,)?) => {
+        $crate::Params::Named(::std::vec![
+            $((::std::string::String::from($name), $crate::Value::from($value))),+
+        ])
+    };
+    ($($value:expr),+ $(,)?) => {
+        $crate::Params::Positional(::std::vec![$($crate::Value::from($value)),+])
+    };
+}
+
 /// Single statement inside a batch request.
+///
+/// With the `serde` feature, a statement serializes as `{"sql": ...,
+/// "params": ..., "want_rows": ...}` (see [`Params`]); a missing `params`
+/// reads as no parameters. Like the params format, this only changes in a
+/// major release, so queued statements can be stored and replayed later.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Statement {
     /// SQL text.
     pub sql: String,
     /// Statement parameters.
+    #[cfg_attr(feature = "serde", serde(default))]
     pub params: Params,
     /// Whether the statement should return rows.
     pub want_rows: bool,
@@ -91,11 +355,208 @@ impl Statement {
             want_rows: false,
         }
     }
+
+    /// Creates a statement with an explicit `want_rows` flag.
+    pub fn with_want_rows<P: Into<Params>>(
+        sql: impl Into<String>,
+        params: P,
+        want_rows: bool,
+    ) -> Self {
+        Self {
+            sql: sql.into(),
+            params: params.into(),
+            want_rows,
+        }
+    }
+
+    /// Creates a statement, guessing `want_rows` from the leading keyword.
+    ///
+    /// `SELECT`, `WITH`, `PRAGMA` and `EXPLAIN` are treated as row-returning;
+    /// everything else as execution-only. Leading whitespace and comments are
+    /// skipped. The guess is purely lexical: `INSERT ... RETURNING` is
+    /// classified as execution-only and a `WITH ... DELETE` as a query, so
+    /// use [`Statement::query`] or [`Statement::execute`] when it matters.
+    pub fn infer<P: Into<Params>>(sql: impl Into<String>, params: P) -> Self {
+        let sql = sql.into();
+        let want_rows = sql::starts_with_row_keyword(&sql);
+        Self::with_want_rows(sql, params, want_rows)
+    }
+
+    /// Parses a batch from a JSON array of
+    /// `{ "kind": "query" | "execute", "sql": "...", "params": ... }`.
+    ///
+    /// `params` is optional and uses the format of
+    /// [`Params::from_json_value`]. Errors name the offending entry, e.g.
+    /// `batch[2] has empty SQL`.
+    pub fn batch_from_json(input: &str) -> Result<Vec<Self>> {
+        #[derive(serde::Deserialize)]
+        struct Entry {
+            kind: String,
+            sql: String,
+            #[serde(default)]
+            params: Option<JsonValue>,
+        }
+
+        let trimmed = input.trim();
+        if trimmed.is_empty() {
+            return Err(BunnyDbError::Decode(
+                "batch JSON cannot be empty".to_owned(),
+            ));
+        }
+        let entries: Vec<Entry> = serde_json::from_str(trimmed)
+            .map_err(|err| BunnyDbError::Decode(format!("invalid batch JSON: {err}")))?;
+
+        entries
+            .into_iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                if entry.sql.trim().is_empty() {
+                    return Err(BunnyDbError::Decode(format!(
+                        "batch[{index}] has empty SQL"
+                    )));
+                }
+                let params = match entry.params {
+                    Some(value) => Params::from_json_value(value).map_err(|err| match err {
+                        BunnyDbError::Decode(message) => {
+                            BunnyDbError::Decode(format!("batch[{index}]: {message}"))
+                        }
+                        other => other,
+                    })?,
+                    None => Params::default(),
+                };
+                if entry.kind.eq_ignore_ascii_case("query") {
+                    Ok(Self::query(entry.sql, params))
+                } else if entry.kind.eq_ignore_ascii_case("execute") {
+                    Ok(Self::execute(entry.sql, params))
+                } else {
+                    Err(BunnyDbError::Decode(format!(
+                        "batch[{index}] invalid kind '{}': expected 'query' or 'execute'",
+                        entry.kind
+                    )))
+                }
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Params, Statement, Value};
+    use std::collections::{BTreeMap, HashMap};
+
+    use serde_json::json;
+
+    use crate::{decode, BunnyDbError, Params, Statement, Value};
+
+    #[test]
+    fn params_from_json_maps_arrays_objects_and_null() {
+        assert_eq!(
+            Params::from_json(r#"[1, 2.5, "kit", true, null, {"blob_base64": "AQI="}]"#)
+                .expect("array"),
+            Params::positional([
+                Value::integer(1),
+                Value::float(2.5),
+                Value::text("kit"),
+                Value::integer(1),
+                Value::null(),
+                Value::blob_base64("AQI="),
+            ])
+        );
+        assert_eq!(
+            Params::from_json_value(json!({ "name": "kit", "id": 7 })).expect("object"),
+            Params::named([("id", Value::integer(7)), ("name", Value::text("kit"))])
+        );
+        assert_eq!(Params::from_json("  ").expect("blank"), Params::default());
+        assert_eq!(Params::from_json("null").expect("null"), Params::default());
+
+        for bad in ["\"kit\"", "[[1]]", r#"[{"other": 1}]"#, "[1,"] {
+            assert!(
+                matches!(Params::from_json(bad), Err(BunnyDbError::Decode(_))),
+                "input: {bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn batch_from_json_builds_statements() {
+        let statements = Statement::batch_from_json(
+            r#"[
+                { "kind": "execute", "sql": "INSERT INTO t (v) VALUES (?)", "params": ["a"] },
+                { "kind": "QUERY", "sql": "SELECT v FROM t WHERE v = :v", "params": { "v": "a" } }
+            ]"#,
+        )
+        .expect("valid batch");
+        assert_eq!(
+            statements,
+            [
+                Statement::execute("INSERT INTO t (v) VALUES (?)", [Value::text("a")]),
+                Statement::query(
+                    "SELECT v FROM t WHERE v = :v",
+                    Params::named([("v", Value::text("a"))])
+                ),
+            ]
+        );
+
+        let err = Statement::batch_from_json(r#"[{ "kind": "run", "sql": "SELECT 1" }]"#)
+            .expect_err("unknown kind");
+        assert!(err.to_string().contains("batch[0] invalid kind 'run'"));
+        let err = Statement::batch_from_json(
+            r#"[{ "kind": "query", "sql": "SELECT ?", "params": [[1]] }]"#,
+        )
+        .expect_err("nested array");
+        assert!(err.to_string().contains("batch[0]: nested arrays"));
+    }
+
+    #[test]
+    fn infer_detects_row_returning_keywords() {
+        for sql in [
+            "SELECT 1",
+            "  with t AS (SELECT 1) SELECT * FROM t",
+            "-- list tables\nPRAGMA table_list",
+            "/* plan */ EXPLAIN QUERY PLAN SELECT 1",
+        ] {
+            assert!(Statement::infer(sql, ()).want_rows, "sql: {sql:?}");
+        }
+        for sql in [
+            "INSERT INTO t VALUES (1)",
+            "\n update t SET v = 1",
+            "-- SELECT\nDELETE FROM t",
+            "",
+        ] {
+            assert!(!Statement::infer(sql, ()).want_rows, "sql: {sql:?}");
+        }
+    }
+
+    #[test]
+    fn with_want_rows_sets_flag() {
+        let statement = Statement::with_want_rows("SELECT 1", (), false);
+        assert!(!statement.want_rows);
+        assert_eq!(statement.sql, "SELECT 1");
+    }
+
+    #[test]
+    fn len_and_kind_helpers() {
+        let empty = Params::default();
+        assert_eq!(empty.len(), 0);
+        assert!(empty.is_empty());
+        assert!(empty.is_positional());
+        assert!(!empty.is_named());
+
+        let positional = Params::positional([Value::integer(1), Value::integer(2)]);
+        assert_eq!(positional.len(), 2);
+        assert!(!positional.is_empty());
+        assert!(positional.is_positional());
+
+        let named = Params::named([("name", Value::text("kit"))]);
+        assert_eq!(named.len(), 1);
+        assert!(named.is_named());
+        assert!(!named.is_positional());
+        assert!(Params::Named(Vec::new()).is_empty());
+
+        let mixed = Params::mixed([Value::integer(1)], [("name", Value::text("kit"))]);
+        assert_eq!(mixed.len(), 2);
+        assert!(!mixed.is_named());
+        assert!(!mixed.is_positional());
+    }
 
     #[test]
     fn positional_from_array() {
@@ -118,6 +579,85 @@ mod tests {
         }
     }
 
+    #[test]
+    fn named_from_maps() {
+        let mut hash = HashMap::new();
+        hash.insert("name".to_owned(), Value::text("kit"));
+        let params = Params::from(hash);
+        assert_eq!(params, Params::named([("name", Value::text("kit"))]));
+
+        let mut ordered = BTreeMap::new();
+        ordered.insert("b", Value::integer(2));
+        ordered.insert("a", Value::integer(1));
+        let params = Params::from(ordered);
+        assert_eq!(
+            params,
+            Params::named([("a", Value::integer(1)), ("b", Value::integer(2))])
+        );
+    }
+
+    #[test]
+    fn empty_map_builds_no_named_args() {
+        let params = Params::from(HashMap::<String, Value>::new());
+        assert_eq!(params, Params::Named(Vec::new()));
+
+        let stmt = decode::build_execute_statement("SELECT 1", params, true, &Default::default())
+            .expect("must build statement");
+        assert!(stmt.named_args.is_none());
+        assert!(stmt.args.is_none());
+    }
+
+    #[test]
+    fn builder_accumulates_positional() {
+        let mut builder = Params::builder();
+        for id in 1..=3 {
+            builder.push(id);
+        }
+        assert_eq!(
+            builder.build(),
+            Params::positional([Value::integer(1), Value::integer(2), Value::integer(3)])
+        );
+    }
+
+    #[test]
+    fn builder_accumulates_named_and_mixed() {
+        let mut builder = Params::builder();
+        builder.bind("name", "kit").bind("age", 30);
+        assert_eq!(
+            builder.clone().build(),
+            Params::named([("name", Value::text("kit")), ("age", Value::integer(30))])
+        );
+
+        builder.push(1);
+        assert!(matches!(builder.build(), Params::Mixed { .. }));
+        assert_eq!(Params::builder().build(), Params::default());
+    }
+
+    #[test]
+    fn params_macro_positional() {
+        let name = String::from("kit");
+        let params = crate::params![1, name.as_str(), 2.5, None::<&str>];
+        assert_eq!(
+            params,
+            Params::positional([
+                Value::integer(1),
+                Value::text("kit"),
+                Value::float(2.5),
+                Value::null(),
+            ])
+        );
+    }
+
+    #[test]
+    fn params_macro_named() {
+        let params = crate::params! { "name" => "kit", ":age" => 30, };
+        assert_eq!(
+            params,
+            Params::named([("name", Value::text("kit")), (":age", Value::integer(30))])
+        );
+        assert_eq!(crate::params! {}, Params::default());
+    }
+
     #[test]
     fn statement_constructors() {
         let query = Statement::query("SELECT 1", ());
@@ -125,4 +665,68 @@ mod tests {
         assert!(query.want_rows);
         assert!(!exec.want_rows);
     }
+
+    #[cfg(feature = "serde")]
+    mod serde_round_trip {
+        use serde_json::json;
+
+        use crate::{Params, Statement, Value};
+
+        fn round_trip(statement: &Statement) -> Statement {
+            let json = serde_json::to_string(statement).expect("must serialize");
+            serde_json::from_str(&json).expect("must deserialize")
+        }
+
+        #[test]
+        fn positional_named_and_empty_params_round_trip() {
+            let statements = [
+                Statement::execute(
+                    "INSERT INTO users (id, name, avatar) VALUES (?, ?, ?)",
+                    [
+                        Value::integer(1),
+                        Value::text("Kit"),
+                        Value::blob_base64("AQID"),
+                    ],
+                ),
+                Statement::query(
+                    "SELECT * FROM users WHERE name = :name AND score > :score",
+                    Params::named([("name", Value::text("Kit")), (":score", Value::float(1.5))]),
+                ),
+                Statement::execute("DELETE FROM sessions", ()),
+                Statement::execute(
+                    "UPDATE users SET name = ? WHERE id = :id",
+                    Params::Mixed {
+                        positional: vec![Value::Null],
+                        named: vec![("id".to_owned(), Value::integer(1))],
+                    },
+                ),
+            ];
+            for statement in &statements {
+                assert_eq!(&round_trip(statement), statement);
+            }
+        }
+
+        #[test]
+        fn serialized_form_is_stable() {
+            let statement =
+                Statement::query("SELECT ?", Params::named([("id", Value::integer(7))]));
+            assert_eq!(
+                serde_json::to_value(&statement).expect("must serialize"),
+                json!({
+                    "sql": "SELECT ?",
+                    "params": { "type": "named", "values": [["id", { "Integer": 7 }]] },
+                    "want_rows": true
+                })
+            );
+            assert_eq!(
+                serde_json::to_value(Params::default()).expect("must serialize"),
+                json!({ "type": "positional", "values": [] })
+            );
+
+            let without_params: Statement =
+                serde_json::from_value(json!({ "sql": "DELETE FROM t", "want_rows": false }))
+                    .expect("params must default");
+            assert_eq!(without_params, Statement::execute("DELETE FROM t", ()));
+        }
+    }
 }
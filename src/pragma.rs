@@ -0,0 +1,113 @@
+use crate::{BunnyDbClient, BunnyDbError, QueryResult, Result, Statement, Value};
+
+impl BunnyDbClient {
+    /// Reads `PRAGMA user_version`, the schema version number applications
+    /// commonly use for migrations.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run(db: bunnydb_http::BunnyDbClient) -> bunnydb_http::Result<()> {
+    /// if db.get_user_version().await? < 2 {
+    ///     db.execute_ddl("ALTER TABLE users ADD COLUMN email TEXT").await?;
+    ///     db.set_user_version(2).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_user_version(&self) -> Result<i64> {
+        let result = self.query("PRAGMA user_version", ()).await?;
+        match pragma_scalar(&result, "user_version")? {
+            Value::Integer(version) => Ok(*version),
+            other => Err(BunnyDbError::Decode(format!(
+                "PRAGMA user_version: expected an integer, got {other:?}"
+            ))),
+        }
+    }
+
+    /// Sets `PRAGMA user_version`.
+    ///
+    /// The value is stored in the database file, so it persists across
+    /// requests.
+    pub async fn set_user_version(&self, version: i64) -> Result<()> {
+        // PRAGMA values cannot be bound; an integer is safe to inline.
+        self.execute(&format!("PRAGMA user_version = {version}"), ())
+            .await?;
+        Ok(())
+    }
+
+    /// Reads `PRAGMA journal_mode`, e.g. `"wal"` or `"delete"`.
+    pub async fn journal_mode(&self) -> Result<String> {
+        let result = self.query("PRAGMA journal_mode", ()).await?;
+        match pragma_scalar(&result, "journal_mode")? {
+            Value::Text(mode) => Ok(mode.clone()),
+            other => Err(BunnyDbError::Decode(format!(
+                "PRAGMA journal_mode: expected text, got {other:?}"
+            ))),
+        }
+    }
+}
+
+impl Statement {
+    /// Builds `PRAGMA foreign_keys = ON` or `OFF`.
+    ///
+    /// The setting belongs to the connection, so it only affects statements
+    /// on the same stream: put it first in a [`BunnyDbClient::batch`], or run
+    /// it on a stream kept open with [`BunnyDbClient::execute_in_stream`].
+    /// SQLite ignores it inside a transaction, so it has no effect in
+    /// [`BunnyDbClient::execute_batch_atomic`] or a
+    /// [`Transaction`](crate::Transaction).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn run(db: bunnydb_http::BunnyDbClient) -> bunnydb_http::Result<()> {
+    /// use bunnydb_http::Statement;
+    ///
+    /// db.batch([
+    ///     Statement::foreign_keys(true),
+    ///     Statement::execute("DELETE FROM orgs WHERE id = 1", ()),
+    /// ])
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn foreign_keys(enabled: bool) -> Self {
+        let state = if enabled { "ON" } else { "OFF" };
+        Self::execute(format!("PRAGMA foreign_keys = {state}"), ())
+    }
+}
+
+/// Returns the first cell of a pragma result.
+fn pragma_scalar<'a>(result: &'a QueryResult, name: &str) -> Result<&'a Value> {
+    result
+        .rows
+        .first()
+        .and_then(|row| row.first())
+        .ok_or_else(|| BunnyDbError::Decode(format!("PRAGMA {name} returned no value")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pragma_scalar;
+    use crate::{BunnyDbError, QueryResult, Value};
+
+    #[test]
+    fn scalar_is_first_cell() {
+        let result = QueryResult::fixture(&["user_version"], vec![vec![Value::integer(7)]]);
+        assert_eq!(
+            pragma_scalar(&result, "user_version").expect("value"),
+            &Value::integer(7)
+        );
+    }
+
+    #[test]
+    fn empty_result_is_rejected() {
+        let err = pragma_scalar(
+            &QueryResult::fixture(&["journal_mode"], Vec::new()),
+            "journal_mode",
+        )
+        .expect_err("must fail");
+        assert!(matches!(err, BunnyDbError::Decode(message) if message.contains("journal_mode")));
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{
+    client::spawn_close_stream,
+    decode::{build_execute_statement, decode_exec_result, decode_query_result},
+    wire::{self, PipelineRequest, Request},
+    BunnyDbClient, BunnyDbError, ExecResult, Params, QueryResult, Result, Statement,
+};
+
+/// Statement whose SQL text is stored server-side and referenced by id.
+///
+/// Created with [`BunnyDbClient::prepare`]. The first call stores the SQL on
+/// a new pipeline stream (`store_sql`) and keeps the stream open; later calls
+/// send only the `sql_id` and arguments on that stream. If the stream is lost
+/// (a failed request, or a response without a baton), the next call stores
+/// the SQL again on a fresh stream. A call rejected because the server
+/// expired the stream fails with [`BunnyDbError::SessionExpired`] and can be
+/// retried as is.
+///
+/// Calls take `&mut self` because a stream handles one request at a time.
+/// Dropping the handle closes the stream in the background when an async
+/// runtime is available; otherwise it stays open until
+/// [`BunnyDbClient::close`] or the server expires it. Prefer
+/// [`PreparedStatement::close`] (or `close_blocking` outside async code) to
+/// close it deterministically and see any error.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run(db: bunnydb_http::BunnyDbClient) -> bunnydb_http::Result<()> {
+/// use bunnydb_http::Value;
+///
+/// let mut insert = db.prepare("INSERT INTO users (name) VALUES (?)");
+/// for name in ["Kit", "Lane"] {
+///     insert.execute([Value::text(name)]).await?;
+/// }
+/// insert.close().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PreparedStatement {
+    client: BunnyDbClient,
+    sql: String,
+    sql_id: i32,
+    baton: Option<String>,
+}
+
+/// One entry of [`BunnyDbClient::batch_items`].
+#[derive(Debug)]
+pub enum BatchItem<'a> {
+    /// Executes a prepared statement, referenced by its stored SQL id.
+    Prepared(&'a PreparedStatement, Params),
+    /// Executes a statement with inline SQL, as in [`BunnyDbClient::batch`].
+    Adhoc(Statement),
+}
+
+impl From<Statement> for BatchItem<'_> {
+    fn from(statement: Statement) -> Self {
+        Self::Adhoc(statement)
+    }
+}
+
+impl BunnyDbClient {
+    /// Creates a [`PreparedStatement`] for `sql`.
+    ///
+    /// Nothing is sent until the statement is first executed.
+    pub fn prepare(&self, sql: impl Into<String>) -> PreparedStatement {
+        PreparedStatement {
+            client: self.clone(),
+            sql: sql.into(),
+            sql_id: self.next_sql_id(),
+            baton: None,
+        }
+    }
+}
+
+impl PreparedStatement {
+    /// Returns the SQL text of this statement.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    pub(crate) fn sql_id(&self) -> i32 {
+        self.sql_id
+    }
+
+    /// Executes the statement and returns rows.
+    pub async fn query<P: Into<Params>>(&mut self, params: P) -> Result<QueryResult> {
+        let (result, meta) = self.run(params.into(), true).await?;
+        decode_query_result(result, &meta, self.client.options())
+    }
+
+    /// Executes the statement and returns execution metadata.
+    pub async fn execute<P: Into<Params>>(&mut self, params: P) -> Result<ExecResult> {
+        let (result, meta) = self.run(params.into(), false).await?;
+        decode_exec_result(result, &meta)
+    }
+
+    /// Releases the stored SQL and closes the stream.
+    ///
+    /// Does nothing when the statement was never executed or its stream is
+    /// already gone, including when the server has expired it.
+    pub async fn close(mut self) -> Result<()> {
+        let Some(baton) = self.baton.take() else {
+            return Ok(());
+        };
+        self.client
+            .close_stream_releasing(&baton, Some(self.sql_id))
+            .await
+    }
+
+    /// Closes the statement like [`PreparedStatement::close`] from
+    /// synchronous code, driving the request on the client's blocking
+    /// runtime (see [`BunnyDbClient::close_blocking`]).
+    ///
+    /// Not available on `wasm32`. Must not be called from within an async
+    /// runtime (doing so panics); use `close().await` there instead.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn close_blocking(self) -> Result<()> {
+        if self.baton.is_none() {
+            return Ok(());
+        }
+        let client = self.client.clone();
+        client.block_on(self.close())?
+    }
+
+    async fn run(
+        &mut self,
+        params: Params,
+        want_rows: bool,
+    ) -> Result<(wire::ExecuteResult, wire::ResponseMeta)> {
+        let mut stmt =
+            build_execute_statement(&self.sql, params, want_rows, self.client.options())?;
+        stmt.sql = None;
+        stmt.sql_id = Some(self.sql_id);
+
+        // Taking the baton means any failure below leaves the handle without
+        // a stream, so the next call starts over with `store_sql`.
+        let baton = self.baton.take();
+        let store = baton.is_none();
+        let mut requests = Vec::with_capacity(2);
+        if store {
+            requests.push(Request::StoreSql {
+                sql_id: self.sql_id,
+                sql: self.sql.clone(),
+            });
+        }
+        requests.push(Request::Execute { stmt });
+        let expected = requests.len();
+
+        let payload = PipelineRequest { baton, requests };
+        let response = self
+            .client
+            .send_pipeline_with_retry(&payload)
+            .await
+            .map_err(|err| {
+                if store {
+                    err
+                } else {
+                    err.into_session_expired()
+                }
+            })?;
+        let meta = response.meta();
+
+        if response.results.len() != expected {
+            return Err(BunnyDbError::Decode(format!(
+                "result count mismatch: expected {expected}, got {}",
+                response.results.len()
+            )));
+        }
+
+        let mut results = response.results.into_iter();
+        if store {
+            let stored = results
+                .next()
+                .ok_or_else(|| BunnyDbError::Decode("missing store_sql result".to_owned()))?;
+            BunnyDbClient::ensure_response_kind(stored, 0, "store_sql", Some(&self.sql))?;
+        }
+        // The SQL is stored; keep the stream even if the statement itself
+        // fails with a SQL error.
+        self.baton.clone_from(&meta.baton);
+
+        let execute = results
+            .next()
+            .ok_or_else(|| BunnyDbError::Decode("missing execute result".to_owned()))?;
+        let result = BunnyDbClient::into_execute_result(execute, expected - 1, Some(&self.sql))?;
+        Ok((result, meta))
+    }
+}
+
+impl Drop for PreparedStatement {
+    fn drop(&mut self) {
+        if let Some(baton) = self.baton.take() {
+            spawn_close_stream(self.client.clone(), baton, Some(self.sql_id));
+        }
+    }
+}
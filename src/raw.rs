@@ -2,6 +2,79 @@
 //!
 //! Enabled with the `raw-mode` feature.
 
+use crate::{BunnyDbClient, Params, QueryResult, Result};
+
 /// Wrapper around raw JSON pipeline response payload.
 #[derive(Clone, Debug, PartialEq)]
 pub struct RawPipelineResponse(pub serde_json::Value);
+
+impl BunnyDbClient {
+    /// Sends an arbitrary JSON body to the pipeline endpoint.
+    ///
+    /// The request goes through the same authorization header, timeout and
+    /// retry handling as the typed API, and the response body is returned as
+    /// parsed JSON without any shape validation. Use this for pipeline
+    /// features the typed API does not cover yet.
+    ///
+    /// ```no_run
+    /// # async fn run(db: bunnydb_http::BunnyDbClient) -> bunnydb_http::Result<()> {
+    /// let response = db
+    ///     .raw_pipeline(serde_json::json!({
+    ///         "requests": [
+    ///             { "type": "describe", "sql": "SELECT * FROM users" },
+    ///             { "type": "close" }
+    ///         ]
+    ///     }))
+    ///     .await?;
+    /// println!("{}", response.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn raw_pipeline(&self, payload: serde_json::Value) -> Result<RawPipelineResponse> {
+        self.send_json_with_retry(&payload)
+            .await
+            .map(RawPipelineResponse)
+    }
+
+    /// Runs a query and returns the result as untyped JSON.
+    ///
+    /// Output shape:
+    ///
+    /// ```json
+    /// {
+    ///   "cols": ["id", "name"],
+    ///   "rows": [[1, "Kit"], [2, "Lane"]],
+    ///   "rows_read": 2,
+    ///   "rows_written": 0,
+    ///   "query_duration_ms": 0.5
+    /// }
+    /// ```
+    ///
+    /// Cell values are converted with [`crate::Value::to_json`]; telemetry
+    /// fields are `null` when the server did not report them.
+    pub async fn query_raw_json<P: Into<Params>>(
+        &self,
+        sql: &str,
+        params: P,
+    ) -> Result<serde_json::Value> {
+        let result = self.query(sql, params).await?;
+        Ok(query_result_to_json(&result))
+    }
+}
+
+fn query_result_to_json(result: &QueryResult) -> serde_json::Value {
+    let cols: Vec<&str> = result.cols.iter().map(|col| col.name.as_str()).collect();
+    let rows: Vec<Vec<serde_json::Value>> = result
+        .rows
+        .iter()
+        .map(|row| row.iter().map(|value| value.to_json()).collect())
+        .collect();
+
+    serde_json::json!({
+        "cols": cols,
+        "rows": rows,
+        "rows_read": result.rows_read,
+        "rows_written": result.rows_written,
+        "query_duration_ms": result.query_duration_ms,
+    })
+}
@@ -0,0 +1,155 @@
+//! NDJSON capture of outgoing pipeline requests (native only).
+//!
+//! Enabled with the `request-log` feature.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+
+use crate::{BunnyDbClient, BunnyDbError, Result};
+
+/// Placeholder written instead of parameter values by
+/// [`BunnyDbClient::with_redacted_request_log`].
+const REDACTED: &str = "<redacted>";
+
+impl BunnyDbClient {
+    /// Appends every outgoing pipeline body to `path` as one line of NDJSON.
+    ///
+    /// Each HTTP attempt is logged, retries included, so the file can be
+    /// replayed against a test database. Only the JSON body is written;
+    /// headers, and with them the authorization credential, never are. The
+    /// log is installed as the request inspector and replaces one set with
+    /// [`BunnyDbClient::with_request_inspector`].
+    ///
+    /// Requests only queue their line; a background thread writes the file
+    /// through a buffer, flushing whenever the queue runs empty and once more
+    /// after the client and all its clones are dropped. Lines of requests
+    /// that just completed may therefore still be in flight, and nothing is
+    /// `fsync`ed. Write errors are ignored so logging never fails a request.
+    /// Opening the file or starting the thread fails with
+    /// [`BunnyDbError::Config`].
+    ///
+    /// **Not available on `wasm32` targets.**
+    pub fn with_request_log(self, path: impl AsRef<Path>) -> Result<Self> {
+        self.install_request_log(path.as_ref(), false)
+    }
+
+    /// Like [`BunnyDbClient::with_request_log`], but replaces every bound
+    /// parameter value with `"<redacted>"`, keeping its type, so SQL text
+    /// and statement shapes are captured without user data.
+    pub fn with_redacted_request_log(self, path: impl AsRef<Path>) -> Result<Self> {
+        self.install_request_log(path.as_ref(), true)
+    }
+
+    fn install_request_log(self, path: &Path, redact: bool) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|err| {
+                BunnyDbError::Config(format!(
+                    "failed to open request log '{}': {err}",
+                    path.display()
+                ))
+            })?;
+        let (lines, queue) = mpsc::channel::<String>();
+        thread::Builder::new()
+            .name("bunnydb-request-log".to_owned())
+            .spawn(move || write_lines(file, queue))
+            .map_err(|err| {
+                BunnyDbError::Config(format!("failed to start request log writer: {err}"))
+            })?;
+        Ok(self.with_request_inspector(Arc::new(move |json: &str| {
+            let mut line = if redact {
+                redact_json(json)
+            } else {
+                json.to_owned()
+            };
+            line.push('\n');
+            let _ = lines.send(line);
+        })))
+    }
+}
+
+/// Writes queued lines until every sender is dropped, flushing whenever the
+/// queue is drained.
+fn write_lines(file: File, queue: Receiver<String>) {
+    let mut writer = BufWriter::new(file);
+    while let Ok(line) = queue.recv() {
+        let _ = writer.write_all(line.as_bytes());
+        while let Ok(line) = queue.try_recv() {
+            let _ = writer.write_all(line.as_bytes());
+        }
+        let _ = writer.flush();
+    }
+}
+
+/// Re-serializes a pipeline body with parameter values redacted.
+fn redact_json(json: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(json) {
+        Ok(mut body) => {
+            redact_values(&mut body);
+            body.to_string()
+        }
+        Err(_) => json.to_owned(),
+    }
+}
+
+/// Replaces the payload of every wire value object: `value` for numbers and
+/// text, `base64` for blobs.
+fn redact_values(node: &mut serde_json::Value) {
+    match node {
+        serde_json::Value::Object(object) => {
+            if object.contains_key("type") {
+                let payload = match object.get("type").and_then(|kind| kind.as_str()) {
+                    Some("blob") => "base64",
+                    _ => "value",
+                };
+                if let Some(value) = object.get_mut(payload) {
+                    *value = serde_json::Value::from(REDACTED);
+                    return;
+                }
+            }
+            object.values_mut().for_each(redact_values);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_values),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::redact_json;
+
+    #[test]
+    fn redaction_keeps_sql_and_types() {
+        let body = r#"{"requests":[{"type":"execute","stmt":{"sql":"SELECT ?","args":[{"type":"text","value":"secret"},{"type":"blob","base64":"AQID"},{"type":"null"}],"named_args":[{"name":":n","value":{"type":"integer","value":"7"}}]}},{"type":"close"}]}"#;
+        let redacted: serde_json::Value = serde_json::from_str(&redact_json(body)).expect("json");
+
+        assert_eq!(
+            redacted,
+            serde_json::json!({
+                "requests": [
+                    {
+                        "type": "execute",
+                        "stmt": {
+                            "sql": "SELECT ?",
+                            "args": [
+                                { "type": "text", "value": "<redacted>" },
+                                { "type": "blob", "base64": "<redacted>" },
+                                { "type": "null" }
+                            ],
+                            "named_args": [
+                                { "name": ":n", "value": { "type": "integer", "value": "<redacted>" } }
+                            ]
+                        }
+                    },
+                    { "type": "close" }
+                ]
+            })
+        );
+    }
+}
@@ -2,7 +2,35 @@
 //!
 //! Enabled with the `row-map` feature.
 
-use crate::{Col, Value};
+use crate::{BunnyDbError, Col, QueryResult, Result, Value};
+
+/// Conversion from a [`RowRef`] into a user type.
+///
+/// Implement this once per struct and use [`QueryResult::map_rows`] to
+/// convert every row. The `try_get_*` accessors on [`RowRef`] return
+/// [`BunnyDbError::Decode`] errors naming the offending column.
+///
+/// ```
+/// use bunnydb_http::{row_map::{FromRow, RowRef}, BunnyDbError};
+///
+/// struct User {
+///     id: i64,
+///     name: String,
+/// }
+///
+/// impl FromRow for User {
+///     fn from_row(row: &RowRef<'_>) -> Result<Self, BunnyDbError> {
+///         Ok(Self {
+///             id: row.try_get_i64("id")?,
+///             name: row.try_get_text("name")?.to_owned(),
+///         })
+///     }
+/// }
+/// ```
+pub trait FromRow: Sized {
+    /// Builds `Self` from one result row.
+    fn from_row(row: &RowRef<'_>) -> Result<Self>;
+}
 
 /// Lightweight row view for name-based access helpers.
 #[derive(Debug)]
@@ -15,7 +43,7 @@ pub struct RowRef<'a> {
 
 impl<'a> RowRef<'a> {
     /// Returns a value by case-insensitive column name.
-    pub fn get(&self, name: &str) -> Option<&Value> {
+    pub fn get(&self, name: &str) -> Option<&'a Value> {
         let idx = self
             .cols
             .iter()
@@ -40,10 +68,309 @@ impl<'a> RowRef<'a> {
     }
 
     /// Returns a text value by column name.
-    pub fn get_text(&self, name: &str) -> Option<&str> {
+    pub fn get_text(&self, name: &str) -> Option<&'a str> {
         match self.get(name)? {
             Value::Text(value) => Some(value.as_str()),
             _ => None,
         }
     }
+
+    /// Returns an integer by column name, or a decode error naming the column.
+    pub fn try_get_i64(&self, name: &str) -> Result<i64> {
+        match self.try_get(name)? {
+            Value::Integer(value) => Ok(*value),
+            other => Err(type_mismatch(name, "integer", other)),
+        }
+    }
+
+    /// Returns a float by column name, or a decode error naming the column.
+    pub fn try_get_f64(&self, name: &str) -> Result<f64> {
+        match self.try_get(name)? {
+            Value::Float(value) => Ok(*value),
+            other => Err(type_mismatch(name, "float", other)),
+        }
+    }
+
+    /// Returns text by column name, or a decode error naming the column.
+    pub fn try_get_text(&self, name: &str) -> Result<&'a str> {
+        match self.try_get(name)? {
+            Value::Text(value) => Ok(value.as_str()),
+            other => Err(type_mismatch(name, "text", other)),
+        }
+    }
+
+    fn try_get(&self, name: &str) -> Result<&'a Value> {
+        self.get(name)
+            .ok_or_else(|| BunnyDbError::Decode(format!("column '{name}' not found in row")))
+    }
+
+    /// Returns a value by zero-based column position.
+    ///
+    /// Useful for unnamed expression columns such as `COUNT(*)`.
+    pub fn at(&self, index: usize) -> Option<&'a Value> {
+        self.values.get(index)
+    }
+
+    /// Returns an integer value by column position.
+    pub fn at_i64(&self, index: usize) -> Option<i64> {
+        match self.at(index)? {
+            Value::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns a float value by column position.
+    pub fn at_f64(&self, index: usize) -> Option<f64> {
+        match self.at(index)? {
+            Value::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns a text value by column position.
+    pub fn at_text(&self, index: usize) -> Option<&'a str> {
+        match self.at(index)? {
+            Value::Text(value) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Returns a nullable integer by column name.
+    ///
+    /// The outer `Option` is `None` when the column does not exist (or holds
+    /// a non-integer, non-null value); the inner one is `None` for SQL `NULL`.
+    pub fn get_opt_i64(&self, name: &str) -> Option<Option<i64>> {
+        match self.get(name)? {
+            Value::Null => Some(None),
+            Value::Integer(value) => Some(Some(*value)),
+            _ => None,
+        }
+    }
+
+    /// Returns a nullable float by column name.
+    ///
+    /// See [`RowRef::get_opt_i64`] for the meaning of the nested `Option`.
+    pub fn get_opt_f64(&self, name: &str) -> Option<Option<f64>> {
+        match self.get(name)? {
+            Value::Null => Some(None),
+            Value::Float(value) => Some(Some(*value)),
+            _ => None,
+        }
+    }
+
+    /// Returns nullable text by column name.
+    ///
+    /// See [`RowRef::get_opt_i64`] for the meaning of the nested `Option`.
+    pub fn get_opt_text(&self, name: &str) -> Option<Option<&'a str>> {
+        match self.get(name)? {
+            Value::Null => Some(None),
+            Value::Text(value) => Some(Some(value.as_str())),
+            _ => None,
+        }
+    }
+
+    /// Converts the row into a JSON object keyed by column name, with values
+    /// converted by [`Value::to_json`].
+    ///
+    /// If several columns share a name (e.g. `SELECT a.id, b.id ...`), the
+    /// last one wins; alias them to keep both.
+    pub fn to_json_object(&self) -> serde_json::Map<String, serde_json::Value> {
+        self.cols
+            .iter()
+            .zip(self.values)
+            .map(|(col, value)| (col.name.clone(), value.to_json()))
+            .collect()
+    }
+}
+
+fn type_mismatch(name: &str, expected: &str, found: &Value) -> BunnyDbError {
+    let found = match found {
+        Value::Null => "null",
+        Value::Integer(_) => "integer",
+        Value::Float(_) => "float",
+        Value::Text(_) => "text",
+        Value::BlobBase64(_) => "blob",
+    };
+    BunnyDbError::Decode(format!(
+        "column '{name}' has type {found}, expected {expected}"
+    ))
+}
+
+impl QueryResult {
+    /// Iterates rows as [`RowRef`] views bound to this result's columns.
+    ///
+    /// ```
+    /// # use bunnydb_http::QueryResult;
+    /// # fn print_names(result: &QueryResult) {
+    /// for row in result.rows_ref() {
+    ///     println!("{:?}", row.get_text("name"));
+    /// }
+    /// # }
+    /// ```
+    pub fn rows_ref(&self) -> impl Iterator<Item = RowRef<'_>> + '_ {
+        self.rows.iter().map(|values| RowRef {
+            cols: &self.cols,
+            values,
+        })
+    }
+
+    /// Converts every row into `T` via [`FromRow`].
+    pub fn map_rows<T: FromRow>(&self) -> Result<Vec<T>> {
+        self.rows_ref().map(|row| T::from_row(&row)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FromRow, RowRef};
+    use crate::{BunnyDbError, Col, QueryResult, Value};
+
+    #[derive(Debug, PartialEq)]
+    struct User {
+        id: i64,
+        name: String,
+    }
+
+    impl FromRow for User {
+        fn from_row(row: &RowRef<'_>) -> Result<Self, BunnyDbError> {
+            Ok(Self {
+                id: row.try_get_i64("id")?,
+                name: row.try_get_text("name")?.to_owned(),
+            })
+        }
+    }
+
+    fn result(rows: Vec<Vec<Value>>) -> QueryResult {
+        QueryResult::fixture(&["id", "name"], rows)
+    }
+
+    fn cols(names: &[&str]) -> Vec<Col> {
+        names
+            .iter()
+            .map(|name| Col {
+                name: (*name).to_owned(),
+                decltype: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn get_opt_distinguishes_missing_from_null() {
+        let cols = cols(&["id", "score", "name", "nick"]);
+        let values = vec![
+            Value::Integer(1),
+            Value::Float(2.5),
+            Value::Text("kit".to_owned()),
+            Value::Null,
+        ];
+        let row = RowRef {
+            cols: &cols,
+            values: &values,
+        };
+
+        assert_eq!(row.get_opt_i64("id"), Some(Some(1)));
+        assert_eq!(row.get_opt_f64("score"), Some(Some(2.5)));
+        assert_eq!(row.get_opt_text("name"), Some(Some("kit")));
+
+        assert_eq!(row.get_opt_i64("nick"), Some(None));
+        assert_eq!(row.get_opt_f64("nick"), Some(None));
+        assert_eq!(row.get_opt_text("nick"), Some(None));
+
+        assert_eq!(row.get_opt_i64("missing"), None);
+        assert_eq!(row.get_opt_f64("missing"), None);
+        assert_eq!(row.get_opt_text("missing"), None);
+    }
+
+    #[test]
+    fn rows_ref_binds_columns() {
+        let result = result(vec![
+            vec![Value::Integer(1), Value::Text("kit".to_owned())],
+            vec![Value::Integer(2), Value::Text("lane".to_owned())],
+        ]);
+
+        let names: Vec<&str> = result
+            .rows_ref()
+            .filter_map(|row| row.get_text("NAME"))
+            .collect();
+        assert_eq!(names, ["kit", "lane"]);
+    }
+
+    #[test]
+    fn positional_and_named_access_on_same_row() {
+        let cols = cols(&["name", "COUNT(*)", "AVG(score)"]);
+        let values = vec![
+            Value::Text("kit".to_owned()),
+            Value::Integer(3),
+            Value::Float(1.5),
+        ];
+        let row = RowRef {
+            cols: &cols,
+            values: &values,
+        };
+
+        assert_eq!(row.at_text(0), row.get_text("name"));
+        assert_eq!(row.at_i64(1), Some(3));
+        assert_eq!(row.at_f64(2), Some(1.5));
+        assert_eq!(row.at(2), Some(&Value::Float(1.5)));
+        assert_eq!(row.at_i64(0), None);
+        assert_eq!(row.at(3), None);
+    }
+
+    #[test]
+    fn to_json_object_keys_values_by_column() {
+        let cols = cols(&["id", "name"]);
+        let values = vec![Value::Integer(1), Value::Text("kit".to_owned())];
+        let row = RowRef {
+            cols: &cols,
+            values: &values,
+        };
+
+        assert_eq!(
+            serde_json::Value::Object(row.to_json_object()),
+            serde_json::json!({ "id": 1, "name": "kit" })
+        );
+    }
+
+    #[test]
+    fn to_json_object_duplicate_columns_last_wins() {
+        let cols = cols(&["id", "id"]);
+        let values = vec![Value::Integer(1), Value::Integer(2)];
+        let row = RowRef {
+            cols: &cols,
+            values: &values,
+        };
+
+        assert_eq!(
+            serde_json::Value::Object(row.to_json_object()),
+            serde_json::json!({ "id": 2 })
+        );
+    }
+
+    #[test]
+    fn map_rows_builds_structs() {
+        let users: Vec<User> = result(vec![vec![Value::Integer(1), Value::Text("kit".to_owned())]])
+            .map_rows()
+            .expect("must map");
+        assert_eq!(
+            users,
+            vec![User {
+                id: 1,
+                name: "kit".to_owned()
+            }]
+        );
+    }
+
+    #[test]
+    fn map_rows_type_mismatch_names_column() {
+        let err = result(vec![vec![
+            Value::Text("one".to_owned()),
+            Value::Text("kit".to_owned()),
+        ]])
+        .map_rows::<User>()
+        .expect_err("must fail");
+        match err {
+            BunnyDbError::Decode(message) => assert!(message.contains("'id'")),
+            _ => panic!("expected decode error"),
+        }
+    }
 }
@@ -0,0 +1,123 @@
+//! Server-side streams outliving their handles, and the runtime used to close
+//! them from synchronous code.
+
+use std::sync::{Mutex, PoisonError};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::{future::Future, sync::OnceLock};
+
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::runtime::{Builder, Runtime};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::BunnyDbError;
+use crate::{BunnyDbClient, Result};
+
+/// Session state shared by a client and its clones.
+#[derive(Default)]
+pub(crate) struct Sessions {
+    /// Streams of handles dropped outside an async runtime, with the id of
+    /// the SQL stored on them, if any.
+    orphaned: Mutex<Vec<(String, Option<i32>)>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    runtime: OnceLock<BlockingRuntime>,
+}
+
+impl Sessions {
+    /// Records a stream for [`BunnyDbClient::close`] to close.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn orphan(&self, baton: String, sql_id: Option<i32>) {
+        self.orphaned
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push((baton, sql_id));
+    }
+
+    fn take_orphaned(&self) -> Vec<(String, Option<i32>)> {
+        std::mem::take(&mut *self.orphaned.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+/// Runtime owned for the lifetime of a client and its clones.
+///
+/// Shut down in the background when dropped, so dropping the last client
+/// inside an async runtime does not panic.
+#[cfg(not(target_arch = "wasm32"))]
+struct BlockingRuntime(Option<Runtime>);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for BlockingRuntime {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.0.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+impl BunnyDbClient {
+    /// Closes the server-side streams still held on behalf of this client.
+    ///
+    /// Queries, executes and batches close their stream in the same request,
+    /// so this only concerns [`PreparedStatement`](crate::PreparedStatement)
+    /// and [`Transaction`](crate::Transaction) handles dropped without
+    /// `close`, `commit` or `rollback`. Inside an async runtime their drop
+    /// closes the stream in the background; outside one it cannot, and the
+    /// stream is kept here (shared by all clones) until this call closes it.
+    /// A dropped transaction's stream is rolled back when it closes.
+    ///
+    /// Prefer explicit `close`/`commit`/`rollback` on the handles: this is a
+    /// safety net for long-lived processes, not a substitute. Every stream is
+    /// attempted; the first error, if any, is returned. Streams the server
+    /// has already expired count as closed.
+    pub async fn close(self) -> Result<()> {
+        let mut first_error = None;
+        for (baton, sql_id) in self.sessions.take_orphaned() {
+            if let Err(err) = self.close_stream_releasing(&baton, sql_id).await {
+                first_error.get_or_insert(err);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// Runs [`BunnyDbClient::close`] from synchronous code on the client's
+    /// blocking runtime.
+    ///
+    /// Not available on `wasm32`. Must not be called from within an async
+    /// runtime (doing so panics); use `close().await` there instead.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn close_blocking(self) -> Result<()> {
+        let client = self.clone();
+        client.block_on(self.close())?
+    }
+
+    /// Drives `future` on a current-thread runtime started on first use and
+    /// owned by the client and its clones, so connections it opens stay
+    /// usable for later blocking calls.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn block_on<F: Future>(&self, future: F) -> Result<F::Output> {
+        let runtime = match self.sessions.runtime.get() {
+            Some(runtime) => runtime,
+            None => {
+                let runtime =
+                    Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                        .map_err(|err| {
+                            BunnyDbError::Config(format!("failed to start runtime: {err}"))
+                        })?;
+                // A runtime started concurrently by another clone wins; ours
+                // is dropped here, outside any async context.
+                let _ = self.sessions.runtime.set(BlockingRuntime(Some(runtime)));
+                self.sessions
+                    .runtime
+                    .get()
+                    .expect("blocking runtime was just set")
+            }
+        };
+        let runtime = runtime
+            .0
+            .as_ref()
+            .expect("blocking runtime is only taken on drop");
+        Ok(runtime.block_on(future))
+    }
+}
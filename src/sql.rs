@@ -0,0 +1,784 @@
+//! Lightweight SQL text scanning shared by the statement helpers.
+//!
+//! This is not a parser: it only understands enough SQLite lexical structure
+//! (string literals, quoted identifiers, comments and parentheses) to find
+//! keywords reliably without being fooled by their appearance inside literals.
+
+use crate::{BunnyDbError, Result};
+
+/// A bare word (keyword or unquoted identifier) found in SQL text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Word<'a> {
+    /// Word text as written.
+    pub text: &'a str,
+    /// Byte offset of the word in the scanned SQL.
+    pub start: usize,
+    /// Parenthesis nesting depth at which the word appears.
+    pub depth: usize,
+}
+
+impl Word<'_> {
+    pub fn is(&self, keyword: &str) -> bool {
+        self.text.eq_ignore_ascii_case(keyword)
+    }
+}
+
+/// Returns every bare word in `sql`, skipping literals and comments.
+pub(crate) fn scan_words(sql: &str) -> Vec<Word<'_>> {
+    let bytes = sql.as_bytes();
+    let mut words = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0usize;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' | b'"' | b'`' => i = skip_quoted(bytes, i, bytes[i]),
+            b'[' => i = skip_quoted(bytes, i, b']'),
+            b'-' if bytes.get(i + 1) == Some(&b'-') => i = skip_line_comment(bytes, i),
+            b'/' if bytes.get(i + 1) == Some(&b'*') => i = skip_block_comment(bytes, i),
+            b'(' => {
+                depth += 1;
+                i += 1;
+            }
+            b')' => {
+                depth = depth.saturating_sub(1);
+                i += 1;
+            }
+            byte if is_word_start(byte) => {
+                let start = i;
+                while i < bytes.len() && is_word_byte(bytes[i]) {
+                    i += 1;
+                }
+                words.push(Word {
+                    text: &sql[start..i],
+                    start,
+                    depth,
+                });
+            }
+            _ => i += 1,
+        }
+    }
+
+    words
+}
+
+/// Returns `true` when the first keyword of `sql` is one that returns rows
+/// (`SELECT`, `WITH`, `PRAGMA` or `EXPLAIN`).
+pub(crate) fn starts_with_row_keyword(sql: &str) -> bool {
+    scan_words(sql).first().is_some_and(|word| {
+        ["SELECT", "WITH", "PRAGMA", "EXPLAIN"]
+            .iter()
+            .any(|keyword| word.is(keyword))
+    })
+}
+
+/// Returns `true` when `sql` contains `keyword` outside parentheses.
+pub(crate) fn has_top_level_keyword(sql: &str, keyword: &str) -> bool {
+    scan_words(sql)
+        .iter()
+        .any(|word| word.depth == 0 && word.is(keyword))
+}
+
+/// Counts `?` placeholders outside literals and comments.
+pub(crate) fn count_positional_placeholders(sql: &str) -> usize {
+    let bytes = sql.as_bytes();
+    let mut count = 0usize;
+    let mut i = 0usize;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' | b'"' | b'`' => i = skip_quoted(bytes, i, bytes[i]),
+            b'[' => i = skip_quoted(bytes, i, b']'),
+            b'-' if bytes.get(i + 1) == Some(&b'-') => i = skip_line_comment(bytes, i),
+            b'/' if bytes.get(i + 1) == Some(&b'*') => i = skip_block_comment(bytes, i),
+            b'?' => {
+                count += 1;
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+
+    count
+}
+
+/// Returns the byte offsets of every `needle` outside literals and comments.
+pub(crate) fn find_outside_literals(sql: &str, needle: &str) -> Vec<usize> {
+    let bytes = sql.as_bytes();
+    let needle = needle.as_bytes();
+    let mut found = Vec::new();
+    let mut i = 0usize;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' | b'"' | b'`' => i = skip_quoted(bytes, i, bytes[i]),
+            b'[' => i = skip_quoted(bytes, i, b']'),
+            b'-' if bytes.get(i + 1) == Some(&b'-') => i = skip_line_comment(bytes, i),
+            b'/' if bytes.get(i + 1) == Some(&b'*') => i = skip_block_comment(bytes, i),
+            _ if !needle.is_empty() && bytes[i..].starts_with(needle) => {
+                found.push(i);
+                i += needle.len();
+            }
+            _ => i += 1,
+        }
+    }
+
+    found
+}
+
+/// Returns `true` when `sql` has a numbered `?NNN` placeholder outside
+/// literals and comments.
+pub(crate) fn has_numbered_placeholder(sql: &str) -> bool {
+    let bytes = sql.as_bytes();
+    find_outside_literals(sql, "?")
+        .into_iter()
+        .any(|at| bytes.get(at + 1).is_some_and(u8::is_ascii_digit))
+}
+
+/// Placeholder kinds found in SQL text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct PlaceholderStyles {
+    /// `?` or `?NNN` placeholders.
+    pub positional: bool,
+    /// `:name`, `@name` or `$name` placeholders.
+    pub named: bool,
+}
+
+/// Reports which placeholder kinds appear outside literals and comments.
+pub(crate) fn scan_placeholder_styles(sql: &str) -> PlaceholderStyles {
+    let bytes = sql.as_bytes();
+    let mut styles = PlaceholderStyles::default();
+    let mut i = 0usize;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' | b'"' | b'`' => i = skip_quoted(bytes, i, bytes[i]),
+            b'[' => i = skip_quoted(bytes, i, b']'),
+            b'-' if bytes.get(i + 1) == Some(&b'-') => i = skip_line_comment(bytes, i),
+            b'/' if bytes.get(i + 1) == Some(&b'*') => i = skip_block_comment(bytes, i),
+            b'?' => {
+                styles.positional = true;
+                i += 1;
+            }
+            b':' | b'@' | b'$'
+                if bytes.get(i + 1).copied().is_some_and(is_word_start)
+                    && !(i > 0 && is_word_byte(bytes[i - 1])) =>
+            {
+                styles.named = true;
+                i += 1;
+                while i < bytes.len() && is_word_byte(bytes[i]) {
+                    i += 1;
+                }
+            }
+            byte if is_word_byte(byte) => {
+                // Skip whole words so a `$` inside an identifier is not a
+                // placeholder.
+                while i < bytes.len() && is_word_byte(bytes[i]) {
+                    i += 1;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+
+    styles
+}
+
+/// Keywords accepted as the start of a schema statement by
+/// [`ensure_ddl`].
+const DDL_KEYWORDS: [&str; 4] = ["CREATE", "ALTER", "DROP", "PRAGMA"];
+
+/// Checks that `sql` starts with a DDL keyword and has no placeholders,
+/// since SQLite does not bind parameters in schema statements.
+pub(crate) fn ensure_ddl(sql: &str) -> Result<()> {
+    let first = scan_words(sql).first().map(|word| word.text);
+    if !first.is_some_and(|word| DDL_KEYWORDS.iter().any(|k| word.eq_ignore_ascii_case(k))) {
+        return Err(BunnyDbError::Decode(format!(
+            "expected a CREATE, ALTER, DROP or PRAGMA statement, got `{}`",
+            first.unwrap_or("")
+        )));
+    }
+    let styles = scan_placeholder_styles(sql);
+    if styles.positional || styles.named {
+        return Err(BunnyDbError::Decode(
+            "DDL statements cannot take parameters; remove the placeholders".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// Statement keywords that can follow a `WITH` clause.
+const DML_KEYWORDS: [&str; 5] = ["SELECT", "INSERT", "REPLACE", "UPDATE", "DELETE"];
+
+/// Rejects an `UPDATE` or `DELETE` (optionally after a `WITH` clause) with
+/// no `WHERE` outside parentheses, so a `WHERE` inside a subquery does not
+/// count. Any other statement passes.
+pub(crate) fn ensure_guarded(sql: &str) -> Result<()> {
+    let words = scan_words(sql);
+    let verb = match words.first() {
+        Some(word) if word.is("WITH") => words
+            .iter()
+            .skip(1)
+            .find(|word| word.depth == 0 && DML_KEYWORDS.iter().any(|k| word.is(k))),
+        first => first,
+    };
+    let Some(verb) = verb.filter(|verb| verb.is("UPDATE") || verb.is("DELETE")) else {
+        return Ok(());
+    };
+    let has_where = words
+        .iter()
+        .any(|word| word.start > verb.start && word.depth == 0 && word.is("WHERE"));
+    if has_where {
+        Ok(())
+    } else {
+        Err(BunnyDbError::Decode(
+            "refusing UPDATE/DELETE without WHERE; use execute() to override".to_owned(),
+        ))
+    }
+}
+
+/// Returns `true` for a plain, optionally dot-qualified identifier such as
+/// `users` or `u.id` (ASCII letters, digits and `_`, not starting with a digit).
+pub(crate) fn is_plain_identifier(name: &str) -> bool {
+    !name.is_empty()
+        && name.split('.').all(|part| {
+            part.chars().next().is_some_and(|c| !c.is_ascii_digit())
+                && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+}
+
+/// Validates `name` as an identifier and returns it double-quoted for safe
+/// interpolation into dynamic SQL such as `CREATE TABLE` or `SELECT ... FROM`.
+///
+/// Accepted names consist of ASCII letters, digits and `_` and do not start
+/// with a digit; anything else fails with [`BunnyDbError::Decode`]. Embedded
+/// double quotes would be doubled, though validation already excludes them.
+///
+/// ```
+/// use bunnydb_http::quote_identifier;
+///
+/// let table = quote_identifier("users_2024")?;
+/// assert_eq!(table, "\"users_2024\"");
+/// assert!(quote_identifier("users; DROP TABLE x").is_err());
+/// # Ok::<(), bunnydb_http::BunnyDbError>(())
+/// ```
+pub fn quote_identifier(name: &str) -> Result<String> {
+    if name.contains('.') || !is_plain_identifier(name) {
+        return Err(BunnyDbError::Decode(format!(
+            "invalid identifier `{name}`: expected letters, digits and `_`, not starting with a digit"
+        )));
+    }
+    Ok(wrap_identifier(name))
+}
+
+/// Replaces `marker` in `sql` with `count` comma-separated `?` placeholders,
+/// for binding a variable-length list to an `IN (...)` clause.
+///
+/// Bind the list positionally in the placeholders' position. The marker must
+/// appear exactly once outside string literals and comments, which are left
+/// untouched; a `count` of zero fails with [`BunnyDbError::Decode`]
+/// because `IN ()` is not valid SQLite.
+///
+/// ```
+/// use bunnydb_http::expand_in_placeholders;
+///
+/// let sql = expand_in_placeholders("SELECT * FROM users WHERE id IN ({in})", "{in}", 3)?;
+/// assert_eq!(sql, "SELECT * FROM users WHERE id IN (?, ?, ?)");
+/// assert!(expand_in_placeholders("... IN ({in})", "{in}", 0).is_err());
+/// # Ok::<(), bunnydb_http::BunnyDbError>(())
+/// ```
+pub fn expand_in_placeholders(sql: &str, marker: &str, count: usize) -> Result<String> {
+    if count == 0 {
+        return Err(BunnyDbError::Decode(format!(
+            "cannot expand `{marker}` for an empty list: `IN ()` is not valid SQL"
+        )));
+    }
+    if marker.is_empty() {
+        return Err(BunnyDbError::Decode("IN marker cannot be empty".to_owned()));
+    }
+    match find_outside_literals(sql, marker)[..] {
+        [at] => Ok(format!(
+            "{}{}{}",
+            &sql[..at],
+            vec!["?"; count].join(", "),
+            &sql[at + marker.len()..]
+        )),
+        [] => Err(BunnyDbError::Decode(format!(
+            "marker `{marker}` not found in SQL"
+        ))),
+        ref found => Err(BunnyDbError::Decode(format!(
+            "marker `{marker}` appears {} times in SQL, expected once",
+            found.len()
+        ))),
+    }
+}
+
+fn wrap_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Splits a multi-statement SQL script on top-level semicolons.
+///
+/// Semicolons inside string literals, quoted identifiers, `--` and `/* */`
+/// comments, and `CREATE TRIGGER ... BEGIN ... END` bodies do not split.
+/// Statements are returned trimmed and without their terminating `;`;
+/// empty statements (including ones holding only comments) are dropped.
+///
+/// ```
+/// use bunnydb_http::split_sql;
+///
+/// let statements = split_sql("CREATE TABLE t (v TEXT); INSERT INTO t VALUES ('a;b');;");
+/// assert_eq!(
+///     statements,
+///     vec!["CREATE TABLE t (v TEXT)", "INSERT INTO t VALUES ('a;b')"]
+/// );
+/// ```
+pub fn split_sql(script: &str) -> Vec<String> {
+    let bytes = script.as_bytes();
+    let mut statements = Vec::new();
+    let mut start = 0usize;
+    let mut first_word: Option<&str> = None;
+    let mut in_trigger = false;
+    let mut block_depth = 0usize;
+    let mut i = 0usize;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' | b'"' | b'`' => i = skip_quoted(bytes, i, bytes[i]),
+            b'[' => i = skip_quoted(bytes, i, b']'),
+            b'-' if bytes.get(i + 1) == Some(&b'-') => i = skip_line_comment(bytes, i),
+            b'/' if bytes.get(i + 1) == Some(&b'*') => i = skip_block_comment(bytes, i),
+            b';' if block_depth == 0 => {
+                push_statement(&mut statements, &script[start..i]);
+                i += 1;
+                start = i;
+                first_word = None;
+                in_trigger = false;
+            }
+            byte if is_word_start(byte) => {
+                let word_start = i;
+                while i < bytes.len() && is_word_byte(bytes[i]) {
+                    i += 1;
+                }
+                let word = &script[word_start..i];
+                let first = *first_word.get_or_insert(word);
+                if first.eq_ignore_ascii_case("CREATE") && word.eq_ignore_ascii_case("TRIGGER") {
+                    in_trigger = true;
+                } else if in_trigger
+                    && (word.eq_ignore_ascii_case("BEGIN")
+                        || (block_depth > 0 && word.eq_ignore_ascii_case("CASE")))
+                {
+                    block_depth += 1;
+                } else if block_depth > 0 && word.eq_ignore_ascii_case("END") {
+                    block_depth -= 1;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    push_statement(&mut statements, &script[start..]);
+
+    statements
+}
+
+fn push_statement(statements: &mut Vec<String>, piece: &str) {
+    let piece = piece.trim();
+    if !is_blank_sql(piece) {
+        statements.push(piece.to_owned());
+    }
+}
+
+/// Returns `true` when `sql` holds nothing but whitespace and comments.
+fn is_blank_sql(sql: &str) -> bool {
+    let bytes = sql.as_bytes();
+    let mut i = 0usize;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'-' if bytes.get(i + 1) == Some(&b'-') => i = skip_line_comment(bytes, i),
+            b'/' if bytes.get(i + 1) == Some(&b'*') => i = skip_block_comment(bytes, i),
+            byte if byte.is_ascii_whitespace() => i += 1,
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// Returns `true` when `sql` ends inside a `--` comment, so text appended on
+/// the same line would be commented out.
+pub(crate) fn ends_in_line_comment(sql: &str) -> bool {
+    let bytes = sql.as_bytes();
+    let mut i = 0usize;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' | b'"' | b'`' => i = skip_quoted(bytes, i, bytes[i]),
+            b'[' => i = skip_quoted(bytes, i, b']'),
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = skip_line_comment(bytes, i);
+                if i >= bytes.len() {
+                    return true;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => i = skip_block_comment(bytes, i),
+            _ => i += 1,
+        }
+    }
+    false
+}
+
+/// Appends `clause` to `sql`, on a new line if `sql` ends in a `--` comment.
+pub(crate) fn append_clause(sql: &str, clause: &str) -> String {
+    let separator = if ends_in_line_comment(sql) { "\n" } else { " " };
+    format!("{sql}{separator}{clause}")
+}
+
+/// Returns the one statement in `sql` as [`split_sql`] finds it: trimmed and
+/// without its terminating `;` or any comments after it.
+///
+/// Fails with [`BunnyDbError::Decode`] when `sql` holds no statement or more
+/// than one, since a clause appended to it would only reach the last.
+pub(crate) fn single_statement(sql: &str) -> Result<String> {
+    let mut statements = split_sql(sql);
+    match statements.len() {
+        1 => Ok(statements.remove(0)),
+        0 => Err(BunnyDbError::Decode("SQL contains no statement".to_owned())),
+        found => Err(BunnyDbError::Decode(format!(
+            "expected a single SQL statement, found {found}"
+        ))),
+    }
+}
+
+pub(crate) fn skip_quoted(bytes: &[u8], start: usize, close: u8) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        if bytes[i] == close {
+            // A doubled quote character is an escaped quote, not the end.
+            if close != b']' && bytes.get(i + 1) == Some(&close) {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+pub(crate) fn skip_line_comment(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 2;
+    while i < bytes.len() && bytes[i] != b'\n' {
+        i += 1;
+    }
+    i
+}
+
+pub(crate) fn skip_block_comment(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 2;
+    while i + 1 < bytes.len() {
+        if bytes[i] == b'*' && bytes[i + 1] == b'/' {
+            return i + 2;
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+fn is_word_start(byte: u8) -> bool {
+    byte.is_ascii_alphabetic() || byte == b'_' || !byte.is_ascii()
+}
+
+fn is_word_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$' || !byte.is_ascii()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        append_clause, count_positional_placeholders, ends_in_line_comment, ensure_ddl,
+        ensure_guarded, expand_in_placeholders, find_outside_literals, has_numbered_placeholder,
+        has_top_level_keyword, quote_identifier, scan_placeholder_styles, scan_words,
+        single_statement, split_sql, wrap_identifier, PlaceholderStyles,
+    };
+    use crate::BunnyDbError;
+
+    #[test]
+    fn split_sql_respects_literals_and_comments() {
+        let script = "
+            -- seed data; not a statement
+            INSERT INTO t VALUES ('a;b', \"c;d\"); /* ; */
+            SELECT 1;;
+            ;
+            -- trailing comment
+        ";
+        assert_eq!(
+            split_sql(script),
+            vec![
+                "-- seed data; not a statement\n            INSERT INTO t VALUES ('a;b', \"c;d\")",
+                "/* ; */\n            SELECT 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn split_sql_keeps_trigger_bodies_together() {
+        let script = "CREATE TABLE log (msg TEXT);
+            CREATE TRIGGER t_after AFTER INSERT ON users BEGIN
+                INSERT INTO log VALUES (CASE WHEN new.name IS NULL THEN 'anon' ELSE new.name END);
+                DELETE FROM log WHERE rowid < 10;
+            END;
+            BEGIN;
+            INSERT INTO users (name) VALUES ('Kit');
+            COMMIT";
+        let statements = split_sql(script);
+        assert_eq!(statements.len(), 5);
+        assert!(statements[1].starts_with("CREATE TRIGGER"));
+        assert!(statements[1].ends_with("END"));
+        assert_eq!(statements[2], "BEGIN");
+        assert_eq!(statements[4], "COMMIT");
+    }
+
+    #[test]
+    fn split_sql_handles_missing_trailing_semicolon_and_empty_input() {
+        assert_eq!(
+            split_sql("SELECT 1; SELECT 2"),
+            vec!["SELECT 1", "SELECT 2"]
+        );
+        assert!(split_sql("  ;; -- nothing\n").is_empty());
+    }
+
+    #[test]
+    fn quote_identifier_wraps_valid_names() {
+        assert_eq!(quote_identifier("users").unwrap(), "\"users\"");
+        assert_eq!(quote_identifier("_tmp_2").unwrap(), "\"_tmp_2\"");
+    }
+
+    #[test]
+    fn quote_identifier_rejects_unsafe_names() {
+        for name in [
+            "",
+            "2fast",
+            "users; DROP TABLE x",
+            "a-b",
+            "a.b",
+            "na\"me",
+            "tbl name",
+        ] {
+            assert!(quote_identifier(name).is_err(), "name: {name:?}");
+        }
+    }
+
+    #[test]
+    fn expand_in_placeholders_fills_list() {
+        assert_eq!(
+            expand_in_placeholders(
+                "SELECT * FROM users WHERE id IN ({in}) AND active = ?",
+                "{in}",
+                3
+            )
+            .expect("must expand"),
+            "SELECT * FROM users WHERE id IN (?, ?, ?) AND active = ?"
+        );
+        assert_eq!(
+            expand_in_placeholders("DELETE FROM t WHERE id IN (:ids)", ":ids", 1)
+                .expect("must expand"),
+            "DELETE FROM t WHERE id IN (?)"
+        );
+    }
+
+    #[test]
+    fn expand_in_placeholders_rejects_bad_input() {
+        let sql = "SELECT * FROM users WHERE id IN ({in})";
+        let err = expand_in_placeholders(sql, "{in}", 0).expect_err("empty list");
+        assert!(matches!(err, BunnyDbError::Decode(message) if message.contains("empty list")));
+        assert!(expand_in_placeholders(sql, "{ids}", 2).is_err());
+        assert!(expand_in_placeholders("{in} OR {in}", "{in}", 2).is_err());
+        assert!(expand_in_placeholders(sql, "", 2).is_err());
+    }
+
+    #[test]
+    fn expand_in_placeholders_ignores_markers_in_literals_and_comments() {
+        let sql = expand_in_placeholders(
+            "SELECT * FROM notes WHERE note <> '{in}' AND id IN ({in}) -- {in}",
+            "{in}",
+            2,
+        )
+        .expect("one marker outside literals");
+        assert_eq!(
+            sql,
+            "SELECT * FROM notes WHERE note <> '{in}' AND id IN (?, ?) -- {in}"
+        );
+        assert!(expand_in_placeholders("SELECT '{in}'", "{in}", 2).is_err());
+    }
+
+    #[test]
+    fn wrap_identifier_doubles_embedded_quotes() {
+        assert_eq!(wrap_identifier("we\"ird"), "\"we\"\"ird\"");
+    }
+
+    #[test]
+    fn scan_skips_literals_and_comments() {
+        let words: Vec<&str> = scan_words("SELECT 'limit' -- LIMIT\n/* LIMIT */ \"LIMIT\" FROM t")
+            .into_iter()
+            .map(|word| word.text)
+            .collect();
+        assert_eq!(words, ["SELECT", "FROM", "t"]);
+    }
+
+    #[test]
+    fn top_level_keyword_ignores_subqueries() {
+        assert!(!has_top_level_keyword(
+            "SELECT * FROM (SELECT * FROM t LIMIT 5)",
+            "limit"
+        ));
+        assert!(has_top_level_keyword("SELECT * FROM t limit 5", "LIMIT"));
+    }
+
+    #[test]
+    fn count_placeholders_skips_literals() {
+        assert_eq!(
+            count_positional_placeholders("SELECT ?, '?', \"?\" -- ?\n FROM t WHERE a = ?"),
+            2
+        );
+    }
+
+    #[test]
+    fn find_outside_literals_skips_literals_and_comments() {
+        let sql = "SELECT '{x}', \"{x}\" /* {x} */ FROM t -- {x}\nWHERE {x} AND {x}";
+        let found = find_outside_literals(sql, "{x}");
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|&at| &sql[at..at + 3] == "{x}"));
+        assert!(has_numbered_placeholder("SELECT ?1, ?"));
+        assert!(!has_numbered_placeholder("SELECT ?, '?1' -- ?2"));
+    }
+
+    #[test]
+    fn placeholder_styles_detect_each_kind() {
+        let positional = scan_placeholder_styles("SELECT * FROM t WHERE a = ? AND b = ?2");
+        assert!(positional.positional && !positional.named);
+
+        for sql in [
+            "SELECT * FROM t WHERE a = :a",
+            "SELECT * FROM t WHERE a = @a",
+            "SELECT * FROM t WHERE a = $a",
+        ] {
+            let named = scan_placeholder_styles(sql);
+            assert!(named.named && !named.positional, "sql: {sql:?}");
+        }
+
+        let mixed = scan_placeholder_styles("SELECT * FROM t WHERE a = ? AND b = :b");
+        assert!(mixed.positional && mixed.named);
+    }
+
+    #[test]
+    fn placeholder_styles_skip_literals_and_identifiers() {
+        assert_eq!(
+            scan_placeholder_styles(
+                "SELECT ':a', \"@b\", [$c], price$usd -- ?\n FROM t /* :d */ WHERE x = 1"
+            ),
+            PlaceholderStyles::default()
+        );
+    }
+
+    #[test]
+    fn ensure_ddl_accepts_schema_statements() {
+        for sql in [
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT DEFAULT '?')",
+            "  alter table users add column email TEXT",
+            "-- cleanup\nDROP INDEX IF EXISTS users_name",
+            "PRAGMA foreign_keys = ON",
+        ] {
+            ensure_ddl(sql).unwrap_or_else(|err| panic!("{sql:?} must pass: {err}"));
+        }
+    }
+
+    #[test]
+    fn ensure_ddl_rejects_parameters_and_other_statements() {
+        for sql in [
+            "CREATE TABLE t (a TEXT DEFAULT ?)",
+            "ALTER TABLE t ADD COLUMN b TEXT DEFAULT :b",
+            "SELECT 1",
+            "INSERT INTO t VALUES (1)",
+            "",
+        ] {
+            assert!(
+                matches!(ensure_ddl(sql), Err(BunnyDbError::Decode(_))),
+                "sql: {sql:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn ensure_guarded_accepts_filtered_writes_and_other_statements() {
+        for sql in [
+            "DELETE FROM users WHERE id = ?",
+            "update users set name = ? where id IN (SELECT id FROM banned)",
+            "UPDATE users SET score = (SELECT max(score) FROM scores) WHERE id = 1",
+            "/* purge */ DELETE FROM sessions -- old ones\n WHERE expires_at < ?",
+            "WITH old AS (SELECT id FROM users) DELETE FROM users WHERE id IN old",
+            "INSERT INTO users (id) VALUES (1) ON CONFLICT DO UPDATE SET id = 1",
+            "SELECT * FROM users",
+            "",
+        ] {
+            ensure_guarded(sql).unwrap_or_else(|err| panic!("{sql:?} must pass: {err}"));
+        }
+    }
+
+    #[test]
+    fn ensure_guarded_rejects_unfiltered_writes() {
+        for sql in [
+            "DELETE FROM users",
+            "delete from users -- WHERE id = 1",
+            "DELETE FROM users /* WHERE id = 1 */",
+            "UPDATE users SET name = 'WHERE'",
+            "UPDATE users SET score = (SELECT score FROM scores WHERE scores.id = users.id)",
+            "WITH RECURSIVE ids(n) AS (SELECT 1 WHERE 1) DELETE FROM users",
+        ] {
+            assert!(
+                matches!(
+                    ensure_guarded(sql),
+                    Err(BunnyDbError::Decode(message)) if message.contains("without WHERE")
+                ),
+                "sql: {sql:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn append_clause_breaks_line_after_trailing_comment() {
+        assert_eq!(append_clause("SELECT 1", "LIMIT 1"), "SELECT 1 LIMIT 1");
+        assert_eq!(
+            append_clause("SELECT 1 -- one", "LIMIT 1"),
+            "SELECT 1 -- one\nLIMIT 1"
+        );
+        assert!(!ends_in_line_comment("SELECT '--' /* -- */"));
+        assert!(!ends_in_line_comment("SELECT 1 -- one\nFROM t"));
+        assert!(ends_in_line_comment("SELECT \"a--b\" -- trailing"));
+    }
+
+    #[test]
+    fn single_statement_strips_terminators_and_trailing_comments() {
+        for sql in [
+            "  SELECT 1;;  \n",
+            "SELECT 1; -- all",
+            "SELECT 1; /* all */",
+            "SELECT 1 /* one */;",
+        ] {
+            let statement = single_statement(sql).expect("single statement");
+            assert!(statement.starts_with("SELECT 1"), "sql: {sql:?}");
+            assert!(!statement.contains(';'), "sql: {sql:?}");
+        }
+        assert_eq!(
+            single_statement("SELECT 1 -- one").expect("single statement"),
+            "SELECT 1 -- one"
+        );
+    }
+
+    #[test]
+    fn single_statement_rejects_several_or_none() {
+        for sql in ["SELECT 1; SELECT 2", "; -- nothing", ""] {
+            assert!(
+                matches!(single_statement(sql), Err(BunnyDbError::Decode(_))),
+                "sql: {sql:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use crate::{
+    client::spawn_close_stream,
+    decode::{decode_exec_result, decode_query_result},
+    sql::quote_identifier,
+    wire::{ExecuteResult, ResponseMeta},
+    BunnyDbClient, BunnyDbError, ExecResult, Params, QueryResult, Result,
+};
+
+/// Interactive transaction on one pipeline stream.
+///
+/// Created with [`BunnyDbClient::begin`], which sends `BEGIN` on a new stream
+/// that stays open; every statement then runs on that stream until
+/// [`Transaction::commit`] or [`Transaction::rollback`] ends it. A SQL error
+/// in one statement leaves the transaction open, so the caller decides
+/// whether to continue or roll back.
+///
+/// Dropping an unfinished transaction closes its stream in the background
+/// when an async runtime is available, which makes the server roll it back;
+/// otherwise the stream stays open until [`BunnyDbClient::close`] or the
+/// server expires it. Prefer an explicit `commit` or `rollback`. Calls on a stream the server has
+/// expired fail with [`BunnyDbError::SessionExpired`]; the transaction is
+/// then lost and must be restarted.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run(db: bunnydb_http::BunnyDbClient) -> bunnydb_http::Result<()> {
+/// use bunnydb_http::Value;
+///
+/// let mut tx = db.begin().await?;
+/// tx.execute("INSERT INTO orders (id) VALUES (?)", [Value::integer(1)]).await?;
+///
+/// let mut line = tx.savepoint("line").await?;
+/// match line.execute("INSERT INTO order_lines (order_id) VALUES (?)", [Value::integer(1)]).await {
+///     Ok(_) => line.release().await?,
+///     // Undo only the line; the order insert above is kept.
+///     Err(_) => line.rollback().await?,
+/// }
+///
+/// tx.commit().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Transaction {
+    client: BunnyDbClient,
+    baton: Option<String>,
+    /// Quoted names of savepoints dropped without `release` or `rollback`,
+    /// in drop order (innermost first); rolled back before the next
+    /// statement.
+    abandoned: Vec<String>,
+}
+
+/// Nested unit of work inside a [`Transaction`], created with
+/// [`Transaction::savepoint`].
+///
+/// [`Savepoint::release`] keeps its changes as part of the enclosing
+/// transaction; [`Savepoint::rollback`] undoes them while the enclosing
+/// transaction stays open. A savepoint dropped without either is rolled back
+/// before the transaction's next statement.
+#[derive(Debug)]
+pub struct Savepoint<'a> {
+    tx: &'a mut Transaction,
+    name: String,
+    finished: bool,
+}
+
+impl BunnyDbClient {
+    /// Starts an interactive [`Transaction`] by sending `BEGIN` on a new
+    /// stream.
+    pub async fn begin(&self) -> Result<Transaction> {
+        let mut baton = None;
+        let (result, meta) = self
+            .run_on_stream(&mut baton, "BEGIN", Params::default(), false, false)
+            .await?;
+        decode_exec_result(result, &meta)?;
+        if baton.is_none() {
+            return Err(BunnyDbError::Decode(
+                "server did not keep the transaction stream open".to_owned(),
+            ));
+        }
+        Ok(Transaction {
+            client: self.clone(),
+            baton,
+            abandoned: Vec::new(),
+        })
+    }
+}
+
+impl Transaction {
+    /// Runs a query inside the transaction.
+    pub async fn query<P: Into<Params>>(&mut self, sql: &str, params: P) -> Result<QueryResult> {
+        self.roll_back_abandoned().await?;
+        let (result, meta) = self.run(sql, params.into(), true, false).await?;
+        decode_query_result(result, &meta, self.client.options())
+    }
+
+    /// Executes a statement inside the transaction.
+    pub async fn execute<P: Into<Params>>(&mut self, sql: &str, params: P) -> Result<ExecResult> {
+        self.roll_back_abandoned().await?;
+        let (result, meta) = self.run(sql, params.into(), false, false).await?;
+        decode_exec_result(result, &meta)
+    }
+
+    /// Starts a savepoint named `name` with `SAVEPOINT`.
+    ///
+    /// The name must be a plain identifier (see
+    /// [`crate::quote_identifier`]); anything else fails with
+    /// [`BunnyDbError::Decode`] before sending.
+    pub async fn savepoint(&mut self, name: &str) -> Result<Savepoint<'_>> {
+        let name = quote_identifier(name)?;
+        self.execute(&format!("SAVEPOINT {name}"), ()).await?;
+        Ok(Savepoint {
+            tx: self,
+            name,
+            finished: false,
+        })
+    }
+
+    /// Sends `COMMIT` and closes the stream.
+    pub async fn commit(mut self) -> Result<()> {
+        self.roll_back_abandoned().await?;
+        self.finish("COMMIT").await
+    }
+
+    /// Sends `ROLLBACK` and closes the stream.
+    pub async fn rollback(mut self) -> Result<()> {
+        self.abandoned.clear();
+        self.finish("ROLLBACK").await
+    }
+
+    async fn finish(&mut self, sql: &str) -> Result<()> {
+        let (result, meta) = self.run(sql, Params::default(), false, true).await?;
+        decode_exec_result(result, &meta)?;
+        self.baton = None;
+        Ok(())
+    }
+
+    async fn run(
+        &mut self,
+        sql: &str,
+        params: Params,
+        want_rows: bool,
+        close: bool,
+    ) -> Result<(ExecuteResult, ResponseMeta)> {
+        if self.baton.is_none() {
+            return Err(BunnyDbError::SessionExpired {
+                message: "transaction stream was lost by an earlier failed request".to_owned(),
+            });
+        }
+        self.client
+            .run_on_stream(&mut self.baton, sql, params, want_rows, close)
+            .await
+    }
+
+    async fn roll_back_abandoned(&mut self) -> Result<()> {
+        while let Some(name) = self.abandoned.first().cloned() {
+            self.run(
+                &format!("ROLLBACK TO {name}"),
+                Params::default(),
+                false,
+                false,
+            )
+            .await?;
+            self.run(&format!("RELEASE {name}"), Params::default(), false, false)
+                .await?;
+            self.abandoned.remove(0);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        if let Some(baton) = self.baton.take() {
+            spawn_close_stream(self.client.clone(), baton, None);
+        }
+    }
+}
+
+impl Savepoint<'_> {
+    /// Runs a query inside the savepoint.
+    pub async fn query<P: Into<Params>>(&mut self, sql: &str, params: P) -> Result<QueryResult> {
+        self.tx.query(sql, params).await
+    }
+
+    /// Executes a statement inside the savepoint.
+    pub async fn execute<P: Into<Params>>(&mut self, sql: &str, params: P) -> Result<ExecResult> {
+        self.tx.execute(sql, params).await
+    }
+
+    /// Starts a savepoint nested in this one.
+    pub async fn savepoint(&mut self, name: &str) -> Result<Savepoint<'_>> {
+        self.tx.savepoint(name).await
+    }
+
+    /// Keeps the savepoint's changes with `RELEASE`.
+    pub async fn release(mut self) -> Result<()> {
+        self.finished = true;
+        let sql = format!("RELEASE {}", self.name);
+        self.tx.execute(&sql, ()).await?;
+        Ok(())
+    }
+
+    /// Undoes the savepoint's changes with `ROLLBACK TO` and `RELEASE`,
+    /// leaving the enclosing transaction open.
+    pub async fn rollback(mut self) -> Result<()> {
+        self.finished = true;
+        let rollback = format!("ROLLBACK TO {}", self.name);
+        self.tx.execute(&rollback, ()).await?;
+        let release = format!("RELEASE {}", self.name);
+        self.tx.execute(&release, ()).await?;
+        Ok(())
+    }
+}
+
+impl Drop for Savepoint<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.tx.abandoned.push(std::mem::take(&mut self.name));
+        }
+    }
+}
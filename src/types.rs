@@ -1,7 +1,10 @@
+use std::time::Duration;
+
 use crate::Value;
 
 /// Column metadata returned by query responses.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Col {
     /// Column name.
     pub name: String,
@@ -11,8 +14,12 @@ pub struct Col {
 
 /// Query response shape.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QueryResult {
     /// Column metadata.
+    ///
+    /// When the server returns rows without column metadata, placeholder
+    /// columns named `col0`, `col1`, ... are synthesized.
     pub cols: Vec<Col>,
     /// Decoded row values.
     pub rows: Vec<Vec<Value>>,
@@ -24,10 +31,73 @@ pub struct QueryResult {
     pub rows_written: Option<u64>,
     /// Optional execution duration in milliseconds.
     pub query_duration_ms: Option<f64>,
+    /// Client-observed wall-clock time of the whole request, including
+    /// network and retries. Always set for results returned by the client.
+    pub elapsed: Option<Duration>,
+    /// Session baton returned by the server, if any.
+    pub baton: Option<String>,
+    /// Base URL the server asked subsequent session requests to use, if any.
+    pub base_url: Option<String>,
+}
+
+impl QueryResult {
+    /// Whether the result has no rows.
+    ///
+    /// Columns are ignored: a query matching nothing still reports its
+    /// columns but is empty.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Number of rows in the result.
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Number of columns in the result, including for an empty result.
+    pub fn col_count(&self) -> usize {
+        self.cols.len()
+    }
+
+    /// Returns the position of a column by case-insensitive name.
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.cols
+            .iter()
+            .position(|col| col.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the values column by column, aligned with `cols`.
+    ///
+    /// Each entry pairs a column name with that column's value in every row.
+    /// Rows shorter than `cols` (which servers should not send) are padded
+    /// with [`Value::Null`]; extra cells beyond `cols` are ignored.
+    pub fn columns(&self) -> Vec<(&str, Vec<&Value>)> {
+        static NULL: Value = Value::Null;
+
+        self.cols
+            .iter()
+            .enumerate()
+            .map(|(index, col)| {
+                let values = self
+                    .rows
+                    .iter()
+                    .map(|row| row.get(index).unwrap_or(&NULL))
+                    .collect();
+                (col.name.as_str(), values)
+            })
+            .collect()
+    }
 }
 
 /// Execute response shape.
+///
+/// The pipeline protocol reports a single `affected_row_count` (SQLite's
+/// `changes()` for the statement); there is no separate total, and `0` means
+/// both "no rows matched" and "not applicable" (DDL, `BEGIN`, ...). Use
+/// [`ExecResult::was_write`] and [`ExecResult::is_ddl_like`] as heuristics
+/// when the distinction matters.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExecResult {
     /// Number of affected rows.
     pub affected_row_count: u64,
@@ -39,10 +109,47 @@ pub struct ExecResult {
     pub rows_read: Option<u64>,
     /// Optional number of rows written during execution.
     pub rows_written: Option<u64>,
+    /// Client-observed wall-clock time of the whole request, including
+    /// network and retries. Always set for results returned by the client.
+    pub elapsed: Option<Duration>,
+    /// Session baton returned by the server, if any.
+    pub baton: Option<String>,
+    /// Base URL the server asked subsequent session requests to use, if any.
+    pub base_url: Option<String>,
+}
+
+impl ExecResult {
+    /// Shorthand for `affected_row_count`.
+    pub fn affected(&self) -> u64 {
+        self.affected_row_count
+    }
+
+    /// Whether the statement evidently changed rows: a non-zero
+    /// `affected_row_count` or a non-zero server-reported `rows_written`.
+    ///
+    /// A write that matched no rows returns `false`, as does DDL.
+    pub fn was_write(&self) -> bool {
+        self.affected_row_count > 0 || self.rows_written.is_some_and(|rows| rows > 0)
+    }
+
+    /// Whether the result looks like a statement that does not modify rows,
+    /// such as `CREATE TABLE`: zero affected rows and no `last_insert_rowid`.
+    ///
+    /// This is a heuristic; an `UPDATE` or `DELETE` matching no rows looks
+    /// the same.
+    pub fn is_ddl_like(&self) -> bool {
+        self.affected_row_count == 0 && self.last_insert_rowid.is_none()
+    }
 }
 
 /// Batch outcome per statement.
+///
+/// New variants may be added in minor releases, so `match`es outside this
+/// crate need a wildcard arm.
 #[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
 pub enum StatementOutcome {
     /// Successful query statement.
     Query(QueryResult),
@@ -56,5 +163,400 @@ pub enum StatementOutcome {
         message: String,
         /// Optional SQL error code.
         code: Option<String>,
+        /// SQL text of the failing statement, truncated to 200 characters.
+        sql: Option<String>,
     },
+    /// Statement result that could not be decoded, reported by
+    /// [`crate::BunnyDbClient::batch_lenient`].
+    DecodeError {
+        /// Index of statement in request batch.
+        request_index: usize,
+        /// Decode error message.
+        message: String,
+    },
+}
+
+impl StatementOutcome {
+    /// Shifts the `request_index` of error outcomes by `offset`, for
+    /// outcomes of a request that carried part of a larger batch.
+    pub(crate) fn offset_request_index(mut self, offset: usize) -> Self {
+        if let Self::SqlError { request_index, .. } | Self::DecodeError { request_index, .. } =
+            &mut self
+        {
+            *request_index += offset;
+        }
+        self
+    }
+
+    /// Returns the query result if this outcome is [`StatementOutcome::Query`].
+    pub fn as_query(&self) -> Option<&QueryResult> {
+        match self {
+            Self::Query(result) => Some(result),
+            _ => None,
+        }
+    }
+
+    /// Returns the execution result if this outcome is [`StatementOutcome::Exec`].
+    pub fn as_exec(&self) -> Option<&ExecResult> {
+        match self {
+            Self::Exec(result) => Some(result),
+            _ => None,
+        }
+    }
+
+    /// Returns `(message, code)` if this outcome is a SQL error.
+    pub fn as_sql_error(&self) -> Option<(&str, Option<&str>)> {
+        match self {
+            Self::SqlError { message, code, .. } => Some((message, code.as_deref())),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` for [`StatementOutcome::SqlError`] and
+    /// [`StatementOutcome::DecodeError`].
+    pub fn is_error(&self) -> bool {
+        matches!(self, Self::SqlError { .. } | Self::DecodeError { .. })
+    }
+}
+
+/// Aggregate of a batch's execution results.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BatchSummary {
+    /// Sum of `affected_row_count` over all [`StatementOutcome::Exec`] outcomes.
+    pub total_affected: u64,
+    /// Last non-null `last_insert_rowid` among the `Exec` outcomes.
+    pub last_insert_rowid: Option<i64>,
+    /// Number of statements in the batch.
+    pub statement_count: usize,
+}
+
+impl BatchSummary {
+    /// Summarizes per-statement outcomes, e.g. from [`crate::BunnyDbClient::batch`].
+    pub fn from_outcomes(outcomes: &[StatementOutcome]) -> Self {
+        outcomes.iter().filter_map(StatementOutcome::as_exec).fold(
+            Self {
+                statement_count: outcomes.len(),
+                ..Self::default()
+            },
+            |summary, exec| Self {
+                total_affected: summary.total_affected + exec.affected_row_count,
+                last_insert_rowid: exec.last_insert_rowid.or(summary.last_insert_rowid),
+                ..summary
+            },
+        )
+    }
+}
+
+/// Server-reported cost of a batch; see [`batch_telemetry`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BatchTelemetry {
+    /// Sum of `rows_read` over all statements.
+    pub rows_read: u64,
+    /// Sum of `rows_written` over all statements.
+    pub rows_written: u64,
+    /// Sum of `query_duration_ms` over all query statements. Exec results
+    /// carry no server duration and add nothing.
+    pub duration_ms: f64,
+}
+
+/// Totals the telemetry fields of per-statement outcomes, e.g. from
+/// [`crate::BunnyDbClient::batch`], for cost monitoring.
+///
+/// Missing values count as zero, and outcomes other than
+/// [`StatementOutcome::Query`] and [`StatementOutcome::Exec`] (such as SQL
+/// errors) contribute nothing.
+pub fn batch_telemetry(outcomes: &[StatementOutcome]) -> BatchTelemetry {
+    outcomes
+        .iter()
+        .fold(BatchTelemetry::default(), |total, outcome| {
+            let (rows_read, rows_written, duration_ms) = match outcome {
+                StatementOutcome::Query(result) => (
+                    result.rows_read,
+                    result.rows_written,
+                    result.query_duration_ms,
+                ),
+                StatementOutcome::Exec(result) => (result.rows_read, result.rows_written, None),
+                _ => (None, None, None),
+            };
+            BatchTelemetry {
+                rows_read: total.rows_read + rows_read.unwrap_or(0),
+                rows_written: total.rows_written + rows_written.unwrap_or(0),
+                duration_ms: total.duration_ms + duration_ms.unwrap_or(0.0),
+            }
+        })
+}
+
+/// Test fixture: a result with the given column names (no declared types)
+/// and rows, and no response metadata.
+#[cfg(test)]
+impl QueryResult {
+    pub(crate) fn fixture(cols: &[&str], rows: Vec<Vec<Value>>) -> Self {
+        Self {
+            cols: cols
+                .iter()
+                .map(|name| Col {
+                    name: (*name).to_owned(),
+                    decltype: None,
+                })
+                .collect(),
+            rows,
+            replication_index: None,
+            rows_read: None,
+            rows_written: None,
+            query_duration_ms: None,
+            elapsed: None,
+            baton: None,
+            base_url: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        batch_telemetry, BatchSummary, BatchTelemetry, Col, ExecResult, QueryResult,
+        StatementOutcome, Value,
+    };
+
+    fn exec(affected_row_count: u64, last_insert_rowid: Option<i64>) -> StatementOutcome {
+        StatementOutcome::Exec(ExecResult {
+            affected_row_count,
+            last_insert_rowid,
+            replication_index: None,
+            rows_read: None,
+            rows_written: None,
+            elapsed: None,
+            baton: None,
+            base_url: None,
+        })
+    }
+
+    #[test]
+    fn exec_heuristics_tell_inserts_from_ddl() {
+        let StatementOutcome::Exec(insert) = exec(1, Some(42)) else {
+            unreachable!()
+        };
+        assert!(insert.was_write());
+        assert!(!insert.is_ddl_like());
+
+        let StatementOutcome::Exec(create_table) = exec(0, None) else {
+            unreachable!()
+        };
+        assert!(!create_table.was_write());
+        assert!(create_table.is_ddl_like());
+
+        let reported_write = ExecResult {
+            rows_written: Some(1),
+            ..create_table
+        };
+        assert!(reported_write.was_write());
+    }
+
+    #[test]
+    fn batch_summary_aggregates_exec_outcomes() {
+        let summary =
+            BatchSummary::from_outcomes(&[exec(2, Some(5)), exec(1, Some(6)), exec(3, None)]);
+        assert_eq!(
+            summary,
+            BatchSummary {
+                total_affected: 6,
+                last_insert_rowid: Some(6),
+                statement_count: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn batch_telemetry_sums_reported_fields() {
+        let query = StatementOutcome::Query(QueryResult {
+            rows_read: Some(10),
+            query_duration_ms: Some(1.5),
+            ..QueryResult::fixture(&[], vec![])
+        });
+        let write = StatementOutcome::Exec(ExecResult {
+            affected_row_count: 2,
+            last_insert_rowid: None,
+            replication_index: None,
+            rows_read: Some(3),
+            rows_written: Some(2),
+            elapsed: None,
+            baton: None,
+            base_url: None,
+        });
+        let failed = StatementOutcome::SqlError {
+            request_index: 3,
+            message: "no such table: logs".to_owned(),
+            code: None,
+            sql: None,
+        };
+
+        assert_eq!(
+            batch_telemetry(&[query, exec(1, None), write, failed]),
+            BatchTelemetry {
+                rows_read: 13,
+                rows_written: 2,
+                duration_ms: 1.5,
+            }
+        );
+        assert_eq!(batch_telemetry(&[]), BatchTelemetry::default());
+    }
+
+    #[test]
+    fn outcome_accessors_match_variant() {
+        let exec = StatementOutcome::Exec(ExecResult {
+            affected_row_count: 1,
+            last_insert_rowid: None,
+            replication_index: None,
+            rows_read: None,
+            rows_written: None,
+            elapsed: None,
+            baton: None,
+            base_url: None,
+        });
+        assert!(exec.as_exec().is_some());
+        assert!(exec.as_query().is_none());
+        assert!(!exec.is_error());
+
+        let error = StatementOutcome::SqlError {
+            request_index: 2,
+            message: "no such table: t".to_owned(),
+            code: Some("SQLITE_ERROR".to_owned()),
+            sql: None,
+        };
+        assert!(error.is_error());
+        assert_eq!(
+            error.as_sql_error(),
+            Some(("no such table: t", Some("SQLITE_ERROR")))
+        );
+        assert!(error.as_exec().is_none());
+    }
+
+    #[test]
+    fn query_result_counts_ignore_columns_for_emptiness() {
+        let mut result = QueryResult {
+            cols: vec![
+                Col {
+                    name: "id".to_owned(),
+                    decltype: Some("INTEGER".to_owned()),
+                },
+                Col {
+                    name: "name".to_owned(),
+                    decltype: Some("TEXT".to_owned()),
+                },
+            ],
+            ..QueryResult::fixture(&[], vec![])
+        };
+        assert!(result.is_empty());
+        assert_eq!(result.row_count(), 0);
+        assert_eq!(result.col_count(), 2);
+
+        result
+            .rows
+            .push(vec![Value::integer(1), Value::text("Kit")]);
+        assert!(!result.is_empty());
+        assert_eq!(result.row_count(), 1);
+    }
+
+    #[test]
+    fn exec_affected_is_row_count() {
+        let StatementOutcome::Exec(result) = exec(3, None) else {
+            unreachable!()
+        };
+        assert_eq!(result.affected(), 3);
+    }
+
+    #[test]
+    fn columns_transposes_rows() {
+        let result = QueryResult::fixture(
+            &["id", "name"],
+            vec![
+                vec![Value::integer(1), Value::text("Kit")],
+                vec![Value::integer(2), Value::text("Lane")],
+                vec![Value::integer(3)],
+            ],
+        );
+
+        let columns = result.columns();
+        assert_eq!(columns.len(), 2);
+        assert_eq!(columns[0].0, "id");
+        assert_eq!(
+            columns[0].1,
+            [&Value::integer(1), &Value::integer(2), &Value::integer(3)]
+        );
+        assert_eq!(columns[1].0, "name");
+        assert_eq!(
+            columns[1].1,
+            [&Value::text("Kit"), &Value::text("Lane"), &Value::Null]
+        );
+    }
+
+    #[test]
+    fn column_index_is_case_insensitive() {
+        let result = QueryResult::fixture(&["id", "Name"], vec![]);
+        assert_eq!(result.column_index("NAME"), Some(1));
+        assert_eq!(result.column_index("missing"), None);
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde_round_trip {
+        use std::time::Duration;
+
+        use super::exec;
+        use crate::{QueryResult, StatementOutcome, Value};
+
+        fn round_trip<T>(value: &T) -> T
+        where
+            T: serde::Serialize + serde::de::DeserializeOwned,
+        {
+            let json = serde_json::to_string(value).expect("must serialize");
+            serde_json::from_str(&json).expect("must deserialize")
+        }
+
+        fn query() -> QueryResult {
+            let mut result =
+                QueryResult::fixture(&["name"], vec![vec![Value::text("Kit")], vec![Value::Null]]);
+            result.cols[0].decltype = Some("TEXT".to_owned());
+            QueryResult {
+                replication_index: Some("7".to_owned()),
+                rows_read: Some(2),
+                query_duration_ms: Some(0.5),
+                elapsed: Some(Duration::from_millis(12)),
+                ..result
+            }
+        }
+
+        #[test]
+        fn result_types_round_trip() {
+            let result = query();
+            assert_eq!(round_trip(&result.cols[0]), result.cols[0]);
+            assert_eq!(round_trip(&result), result);
+
+            let exec = exec(3, Some(9));
+            assert_eq!(round_trip(&exec), exec);
+            assert_eq!(
+                round_trip(exec.as_exec().unwrap()),
+                *exec.as_exec().unwrap()
+            );
+        }
+
+        #[test]
+        fn statement_outcome_is_tagged() {
+            let outcomes = vec![
+                StatementOutcome::Query(query()),
+                exec(1, None),
+                StatementOutcome::SqlError {
+                    request_index: 1,
+                    message: "no such table: t".to_owned(),
+                    code: Some("SQLITE_ERROR".to_owned()),
+                    sql: Some("SELECT * FROM t".to_owned()),
+                },
+            ];
+            assert_eq!(round_trip(&outcomes), outcomes);
+
+            let json = serde_json::to_value(&outcomes[2]).expect("must serialize");
+            assert_eq!(json["type"], "sql_error");
+            assert_eq!(json["request_index"], 1);
+            assert_eq!(serde_json::to_value(&outcomes[1]).unwrap()["type"], "exec");
+        }
+    }
 }
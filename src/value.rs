@@ -1,5 +1,65 @@
+use std::borrow::Cow;
+
+use base64::{
+    engine::{general_purpose, GeneralPurpose},
+    Engine as _,
+};
+
+/// Base64 alphabet and padding used for [`Value::BlobBase64`] payloads.
+///
+/// The pipeline wire format uses [`Base64Mode::Standard`]. With
+/// [`crate::ClientOptions::base64_mode`] set to another mode, blob parameters
+/// are expected in that mode and result blobs are returned in it; the client
+/// converts to and from the wire format.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Base64Mode {
+    /// Standard alphabet (`+`, `/`) with `=` padding.
+    #[default]
+    Standard,
+    /// Standard alphabet without padding.
+    StandardNoPad,
+    /// URL-safe alphabet (`-`, `_`) with `=` padding.
+    UrlSafe,
+    /// URL-safe alphabet without padding.
+    UrlSafeNoPad,
+}
+
+impl Base64Mode {
+    /// Encodes bytes in this mode.
+    pub fn encode(self, bytes: impl AsRef<[u8]>) -> String {
+        self.engine().encode(bytes)
+    }
+
+    /// Decodes text in this mode, failing with [`crate::BunnyDbError::Decode`]
+    /// on characters or padding the mode does not allow.
+    pub fn decode(self, input: impl AsRef<[u8]>) -> crate::Result<Vec<u8>> {
+        self.engine()
+            .decode(input)
+            .map_err(|err| crate::BunnyDbError::Decode(format!("invalid {self:?} base64: {err}")))
+    }
+
+    fn engine(self) -> &'static GeneralPurpose {
+        match self {
+            Self::Standard => &general_purpose::STANDARD,
+            Self::StandardNoPad => &general_purpose::STANDARD_NO_PAD,
+            Self::UrlSafe => &general_purpose::URL_SAFE,
+            Self::UrlSafeNoPad => &general_purpose::URL_SAFE_NO_PAD,
+        }
+    }
+}
+
 /// Logical value type used for SQL parameters and decoded rows.
+///
+/// New variants may be added in minor releases, so `match`es outside this
+/// crate need a wildcard arm; prefer the constructors such as
+/// [`Value::integer`] for building values.
+///
+/// Values serialize by variant name, e.g. `"Null"`, `{"Integer": 7}` or
+/// `{"Text": "kit"}`; with the `serde` feature they deserialize from the same
+/// form.
 #[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[non_exhaustive]
 pub enum Value {
     /// SQL null.
     Null,
@@ -34,10 +94,116 @@ impl Value {
         Self::Text(value.into())
     }
 
+    /// Creates a text value from bytes that should be UTF-8 but may not be,
+    /// replacing invalid sequences with `U+FFFD`.
+    ///
+    /// Meant for textual data of uncertain encoding, such as file names.
+    /// Binary data should use [`Value::blob`], which keeps every byte.
+    pub fn text_lossy(bytes: &[u8]) -> Self {
+        Self::Text(String::from_utf8_lossy(bytes).into_owned())
+    }
+
     /// Creates a base64 blob value.
     pub fn blob_base64(value: impl Into<String>) -> Self {
         Self::BlobBase64(value.into())
     }
+
+    /// Creates a blob value from raw bytes, base64-encoding them in the
+    /// standard mode.
+    ///
+    /// `Vec<u8>` and `&[u8]` also convert with `into()`.
+    pub fn blob(bytes: impl AsRef<[u8]>) -> Self {
+        Self::blob_with_mode(bytes, Base64Mode::Standard)
+    }
+
+    /// Creates a blob value from raw bytes encoded in `mode`, for clients
+    /// configured with a non-default [`crate::ClientOptions::base64_mode`].
+    pub fn blob_with_mode(bytes: impl AsRef<[u8]>, mode: Base64Mode) -> Self {
+        Self::BlobBase64(mode.encode(bytes))
+    }
+
+    /// Decodes a standard base64 blob value to its raw bytes.
+    ///
+    /// Returns `None` for non-blob values and for blobs whose payload is not
+    /// valid standard base64.
+    pub fn as_bytes(&self) -> Option<Vec<u8>> {
+        self.as_bytes_with_mode(Base64Mode::Standard)
+    }
+
+    /// Decodes a blob value encoded in `mode` to its raw bytes.
+    pub fn as_bytes_with_mode(&self, mode: Base64Mode) -> Option<Vec<u8>> {
+        match self {
+            Self::BlobBase64(value) => mode.decode(value).ok(),
+            _ => None,
+        }
+    }
+
+    /// Converts the value to plain JSON.
+    ///
+    /// Null, numbers and text map to their JSON counterparts; blobs become
+    /// their base64 string.
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::Null => serde_json::Value::Null,
+            Self::Integer(value) => serde_json::Value::from(*value),
+            Self::Float(value) => serde_json::Value::from(*value),
+            Self::Text(value) => serde_json::Value::from(value.as_str()),
+            Self::BlobBase64(value) => serde_json::Value::from(value.as_str()),
+        }
+    }
+
+    /// Compares two values, treating integers and floats numerically.
+    ///
+    /// `Integer(1)` equals `Float(1.0)`, which `==` does not. The comparison
+    /// is exact, so integers beyond 2^53 only equal floats of the same value.
+    /// Everything else compares like `==`: text is never coerced to a number
+    /// (`Text("1")` does not equal `Integer(1)`), and text, blobs and null
+    /// only equal values of the same kind.
+    pub fn numeric_eq(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Self::Integer(int), Self::Float(float)) | (Self::Float(float), Self::Integer(int)) => {
+                integer_eq_float(*int, *float)
+            }
+            _ => self == other,
+        }
+    }
+}
+
+fn integer_eq_float(int: i64, float: f64) -> bool {
+    // `i64::MAX as f64` rounds up to 2^63, so the upper bound is exclusive.
+    float.fract() == 0.0
+        && float >= i64::MIN as f64
+        && float < i64::MAX as f64
+        && float as i64 == int
+}
+
+/// Requires the `value-hash` feature.
+///
+/// SQL values have no `NaN`s in practice (encoding rejects non-finite floats
+/// by default), but a hand-built `Value::Float(f64::NAN)` is not equal to
+/// itself, which breaks `Eq` reflexivity; do not use such values as keys.
+#[cfg(feature = "value-hash")]
+impl Eq for Value {}
+
+/// Requires the `value-hash` feature.
+///
+/// Floats hash by their bit pattern, with `-0.0` hashed like `0.0` since the
+/// two compare equal. Hashing is consistent with `==`, so `Integer(1)` and
+/// `Float(1.0)` are distinct keys.
+#[cfg(feature = "value-hash")]
+impl std::hash::Hash for Value {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Self::Null => {}
+            Self::Integer(value) => value.hash(state),
+            Self::Float(value) => {
+                let value = if *value == 0.0 { 0.0 } else { *value };
+                value.to_bits().hash(state);
+            }
+            Self::Text(value) | Self::BlobBase64(value) => value.hash(state),
+        }
+    }
 }
 
 impl From<String> for Value {
@@ -52,6 +218,24 @@ impl From<&str> for Value {
     }
 }
 
+impl From<&String> for Value {
+    fn from(value: &String) -> Self {
+        Self::Text(value.clone())
+    }
+}
+
+impl From<Cow<'_, str>> for Value {
+    fn from(value: Cow<'_, str>) -> Self {
+        Self::Text(value.into_owned())
+    }
+}
+
+impl From<Box<str>> for Value {
+    fn from(value: Box<str>) -> Self {
+        Self::Text(value.into())
+    }
+}
+
 impl From<i64> for Value {
     fn from(value: i64) -> Self {
         Self::Integer(value)
@@ -70,9 +254,29 @@ impl From<f64> for Value {
     }
 }
 
+impl From<Vec<u8>> for Value {
+    fn from(value: Vec<u8>) -> Self {
+        Self::blob(value)
+    }
+}
+
+impl From<&[u8]> for Value {
+    fn from(value: &[u8]) -> Self {
+        Self::blob(value)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Null, Into::into)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::Value;
+    use std::borrow::Cow;
+
+    use crate::{Base64Mode, Params, Value};
 
     #[test]
     fn helper_constructors() {
@@ -85,4 +289,139 @@ mod tests {
             Value::BlobBase64("AQID".to_owned())
         );
     }
+
+    #[test]
+    fn text_lossy_replaces_invalid_utf8() {
+        assert_eq!(
+            Value::text_lossy(b"caf\xc3\xa9 \xff\xfe ok"),
+            Value::text("café \u{fffd}\u{fffd} ok")
+        );
+        assert_eq!(Value::text_lossy(b"plain"), Value::text("plain"));
+    }
+
+    #[test]
+    fn to_json_produces_plain_values() {
+        assert_eq!(Value::null().to_json(), serde_json::Value::Null);
+        assert_eq!(Value::integer(7).to_json(), serde_json::json!(7));
+        assert_eq!(Value::float(1.5).to_json(), serde_json::json!(1.5));
+        assert_eq!(Value::text("kit").to_json(), serde_json::json!("kit"));
+        assert_eq!(
+            Value::blob_base64("AQID").to_json(),
+            serde_json::json!("AQID")
+        );
+    }
+
+    #[test]
+    fn numeric_eq_compares_integers_and_floats() {
+        assert!(Value::integer(1).numeric_eq(&Value::float(1.0)));
+        assert!(Value::float(-3.0).numeric_eq(&Value::integer(-3)));
+        assert!(Value::float(2.5).numeric_eq(&Value::float(2.5)));
+        assert!(!Value::integer(1).numeric_eq(&Value::float(1.5)));
+        assert!(!Value::integer(i64::MAX).numeric_eq(&Value::float(i64::MAX as f64)));
+        assert!(Value::integer(1 << 60).numeric_eq(&Value::float((1i64 << 60) as f64)));
+    }
+
+    #[test]
+    fn numeric_eq_does_not_coerce_text() {
+        assert!(!Value::text("1").numeric_eq(&Value::integer(1)));
+        assert!(!Value::integer(1).numeric_eq(&Value::text("1.0")));
+        assert!(!Value::blob_base64("AQ==").numeric_eq(&Value::text("AQ==")));
+        assert!(!Value::null().numeric_eq(&Value::integer(0)));
+        assert!(Value::text("kit").numeric_eq(&Value::text("kit")));
+        assert!(Value::null().numeric_eq(&Value::null()));
+    }
+
+    #[test]
+    fn option_maps_none_to_null() {
+        assert_eq!(Value::from(None::<i64>), Value::Null);
+        assert_eq!(Value::from(Some("kit")), Value::text("kit"));
+    }
+
+    #[test]
+    fn borrowed_and_boxed_strings_convert_to_text() {
+        let owned = String::from("kit");
+        assert_eq!(Value::from(&owned), Value::text("kit"));
+        assert_eq!(Value::from(Cow::Borrowed("kit")), Value::text("kit"));
+        assert_eq!(
+            Value::from(Cow::<str>::Owned(owned.clone())),
+            Value::text("kit")
+        );
+        assert_eq!(Value::from(Box::<str>::from("kit")), Value::text("kit"));
+        assert_eq!(Value::from(Some(&owned)), Value::text("kit"));
+    }
+
+    #[test]
+    fn bytes_convert_to_blobs_and_back() {
+        let bytes = vec![0u8, 1, 2, 255];
+        let value = Value::from(bytes.clone());
+        assert_eq!(value, Value::blob_base64("AAEC/w=="));
+        assert_eq!(value.as_bytes(), Some(bytes.clone()));
+        assert_eq!(Value::from(&bytes[..2]).as_bytes(), Some(vec![0, 1]));
+        assert_eq!(Value::blob(b"").as_bytes(), Some(Vec::new()));
+
+        let params = Params::positional([bytes.into(), "kit".into()]);
+        assert_eq!(
+            params,
+            Params::positional([Value::blob_base64("AAEC/w=="), Value::text("kit")])
+        );
+    }
+
+    #[test]
+    fn blob_modes_round_trip_and_do_not_mix() {
+        let bytes = [0xfb, 0xff, 0x3e];
+        let standard = Value::blob_with_mode(bytes, Base64Mode::Standard);
+        let url_safe = Value::blob_with_mode(bytes, Base64Mode::UrlSafe);
+        assert_eq!(standard, Value::blob_base64("+/8+"));
+        assert_eq!(url_safe, Value::blob_base64("-_8-"));
+
+        for mode in [
+            Base64Mode::Standard,
+            Base64Mode::StandardNoPad,
+            Base64Mode::UrlSafe,
+            Base64Mode::UrlSafeNoPad,
+        ] {
+            let value = Value::blob_with_mode(b"\xfb\xff", mode);
+            assert_eq!(
+                value.as_bytes_with_mode(mode),
+                Some(vec![0xfb, 0xff]),
+                "{mode:?}"
+            );
+        }
+
+        assert_eq!(url_safe.as_bytes(), None);
+        assert_eq!(standard.as_bytes_with_mode(Base64Mode::UrlSafe), None);
+        let padded = Value::blob_with_mode([1u8], Base64Mode::Standard);
+        assert_eq!(padded.as_bytes_with_mode(Base64Mode::StandardNoPad), None);
+    }
+
+    #[test]
+    fn as_bytes_rejects_non_blobs_and_bad_base64() {
+        assert_eq!(Value::text("AAEC").as_bytes(), None);
+        assert_eq!(Value::blob_base64("not base64!").as_bytes(), None);
+    }
+
+    #[cfg(feature = "value-hash")]
+    #[test]
+    fn values_work_as_hash_set_keys() {
+        use std::collections::HashSet;
+
+        let keys: HashSet<Value> = [
+            Value::integer(1),
+            Value::integer(2),
+            Value::integer(1),
+            Value::text("kit"),
+            Value::text("kit"),
+            Value::float(0.0),
+            Value::float(-0.0),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(keys.len(), 4);
+        assert!(keys.contains(&Value::integer(2)));
+        assert!(keys.contains(&Value::text("kit")));
+        assert!(!keys.contains(&Value::text("lane")));
+        assert!(!keys.contains(&Value::float(1.0)));
+        assert!(!keys.contains(&Value::integer(0)));
+    }
 }
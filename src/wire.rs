@@ -1,20 +1,69 @@
-use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use serde::{Deserialize, Deserializer, Serialize};
 
 #[derive(Debug, Serialize)]
 pub struct PipelineRequest {
+    /// Baton of an open stream; `None` starts a new stream.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baton: Option<String>,
     pub requests: Vec<Request>,
 }
 
+impl PipelineRequest {
+    /// Number of SQL statements carried by the pipeline, across execute
+    /// requests and batch steps.
+    #[cfg(feature = "tracing")]
+    pub fn statement_count(&self) -> usize {
+        self.requests
+            .iter()
+            .map(|request| match request {
+                Request::Execute { .. } | Request::Describe { .. } => 1,
+                Request::Batch { batch } => batch.steps.len(),
+                Request::Close {} | Request::StoreSql { .. } | Request::CloseSql { .. } => 0,
+            })
+            .sum()
+    }
+}
+
 #[derive(Debug, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Request {
     Execute { stmt: ExecuteStatement },
+    Batch { batch: Batch },
     Close {},
+    StoreSql { sql_id: i32, sql: String },
+    CloseSql { sql_id: i32 },
+    Describe { sql: String },
+}
+
+#[derive(Debug, Serialize)]
+pub struct Batch {
+    pub steps: Vec<BatchStep>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchStep {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<BatchCondition>,
+    pub stmt: ExecuteStatement,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BatchCondition {
+    Ok { step: usize },
+    Not { cond: Box<BatchCondition> },
 }
 
 #[derive(Debug, Serialize)]
 pub struct ExecuteStatement {
-    pub sql: String,
+    /// SQL text; `None` when the statement references stored SQL.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sql: Option<String>,
+    /// Id of SQL previously stored on the stream with `store_sql`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sql_id: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub args: Option<Vec<Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -32,13 +81,22 @@ pub struct NamedArg {
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Value {
     Null {},
-    Integer { value: String },
-    Float { value: String },
-    Text { value: String },
-    Blob { base64: String },
+    Integer {
+        #[serde(deserialize_with = "string_or_number")]
+        value: String,
+    },
+    Float {
+        #[serde(deserialize_with = "string_or_number")]
+        value: String,
+    },
+    Text {
+        value: String,
+    },
+    Blob {
+        base64: String,
+    },
 }
 
-#[allow(dead_code)]
 #[derive(Debug, Deserialize)]
 pub struct PipelineResponse {
     #[serde(default)]
@@ -46,6 +104,28 @@ pub struct PipelineResponse {
     #[serde(default)]
     pub base_url: Option<String>,
     pub results: Vec<PipelineResult>,
+    /// Client-measured duration of the request; not part of the wire format.
+    #[serde(skip)]
+    pub elapsed: Option<Duration>,
+}
+
+impl PipelineResponse {
+    /// Response-level fields copied onto every decoded result.
+    pub fn meta(&self) -> ResponseMeta {
+        ResponseMeta {
+            baton: self.baton.clone(),
+            base_url: self.base_url.clone(),
+            elapsed: self.elapsed,
+        }
+    }
+}
+
+/// Response-level metadata shared by all results of one pipeline call.
+#[derive(Clone, Debug, Default)]
+pub struct ResponseMeta {
+    pub baton: Option<String>,
+    pub base_url: Option<String>,
+    pub elapsed: Option<Duration>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -58,7 +138,7 @@ pub struct PipelineResult {
     pub error: Option<PipelineError>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct PipelineError {
     pub message: String,
     #[serde(default)]
@@ -69,8 +149,17 @@ pub struct PipelineError {
 pub struct ResponseEnvelope {
     #[serde(rename = "type")]
     pub kind: String,
+    /// Result payload; its shape depends on `kind`.
+    #[serde(default)]
+    pub result: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BatchResult {
     #[serde(default)]
-    pub result: Option<ExecuteResult>,
+    pub step_results: Vec<Option<ExecuteResult>>,
+    #[serde(default)]
+    pub step_errors: Vec<Option<PipelineError>>,
 }
 
 #[allow(dead_code)]
@@ -80,9 +169,9 @@ pub struct ExecuteResult {
     pub cols: Vec<Col>,
     #[serde(default)]
     pub rows: Vec<Vec<Value>>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "u64_from_string_or_number")]
     pub affected_row_count: u64,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "optional_string_or_number")]
     pub last_insert_rowid: Option<String>,
     #[serde(default)]
     pub replication_index: Option<String>,
@@ -94,9 +183,53 @@ pub struct ExecuteResult {
     pub query_duration_ms: Option<f64>,
 }
 
+/// Result of a `describe` request; parameter and flag fields are ignored.
+#[derive(Debug, Deserialize)]
+pub struct DescribeResult {
+    #[serde(default)]
+    pub cols: Vec<Col>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Col {
     pub name: String,
     #[serde(default)]
     pub decltype: Option<String>,
 }
+
+/// A JSON scalar that servers may send either as a string or as a number.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumber {
+    String(String),
+    Number(serde_json::Number),
+}
+
+impl StringOrNumber {
+    fn into_string(self) -> String {
+        match self {
+            Self::String(value) => value,
+            Self::Number(value) => value.to_string(),
+        }
+    }
+}
+
+fn string_or_number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    StringOrNumber::deserialize(deserializer).map(StringOrNumber::into_string)
+}
+
+fn optional_string_or_number<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    Option::<StringOrNumber>::deserialize(deserializer)
+        .map(|value| value.map(StringOrNumber::into_string))
+}
+
+fn u64_from_string_or_number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    match StringOrNumber::deserialize(deserializer)? {
+        StringOrNumber::String(value) => value.parse().map_err(serde::de::Error::custom),
+        StringOrNumber::Number(value) => value
+            .as_u64()
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid row count {value}"))),
+    }
+}
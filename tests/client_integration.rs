@@ -1,5 +1,6 @@
 use std::{
     collections::VecDeque,
+    io::Read,
     sync::{
         atomic::{AtomicUsize, Ordering},
         Arc, Mutex,
@@ -7,10 +8,19 @@ use std::{
     time::Duration,
 };
 
-use axum::{extract::State, http::StatusCode, response::IntoResponse, routing::post, Json, Router};
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::post,
+    Json, Router,
+};
 use bunnydb_http::{
-    BunnyDbClient, BunnyDbError, ClientOptions, Statement, StatementOutcome, Value,
+    BatchItem, BatchSummary, BunnyDbClient, BunnyDbError, CancellationToken, ClientOptions, Params,
+    QueryPlanStep, RequestMetrics, RequestSize, Statement, StatementOutcome, Value,
 };
+use flate2::read::GzDecoder;
 use serde_json::{json, Value as JsonValue};
 
 #[derive(Clone)]
@@ -18,6 +28,10 @@ struct MockResponse {
     status: StatusCode,
     body: JsonValue,
     delay: Duration,
+    /// When set, `body` must be a JSON string sent verbatim with this type.
+    content_type: Option<&'static str>,
+    /// Extra response headers.
+    headers: Vec<(&'static str, &'static str)>,
 }
 
 impl MockResponse {
@@ -26,6 +40,15 @@ impl MockResponse {
             status,
             body,
             delay: Duration::from_millis(0),
+            content_type: None,
+            headers: Vec::new(),
+        }
+    }
+
+    fn text(status: StatusCode, content_type: &'static str, body: &str) -> Self {
+        Self {
+            content_type: Some(content_type),
+            ..Self::json(status, JsonValue::String(body.to_owned()))
         }
     }
 
@@ -33,16 +56,56 @@ impl MockResponse {
         self.delay = delay;
         self
     }
+
+    fn with_header(mut self, name: &'static str, value: &'static str) -> Self {
+        self.headers.push((name, value));
+        self
+    }
 }
 
 #[derive(Clone)]
 struct MockState {
     responses: Arc<Mutex<VecDeque<MockResponse>>>,
     hits: Arc<AtomicUsize>,
+    bodies: Arc<Mutex<Vec<JsonValue>>>,
+    authorizations: Arc<Mutex<Vec<String>>>,
 }
 
-async fn pipeline_handler(State(state): State<MockState>, _body: String) -> impl IntoResponse {
+async fn pipeline_handler(
+    State(state): State<MockState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
     state.hits.fetch_add(1, Ordering::SeqCst);
+    if let Some(value) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+    {
+        state
+            .authorizations
+            .lock()
+            .expect("authorization log mutex must not be poisoned")
+            .push(value.to_owned());
+    }
+    let gzipped = headers
+        .get(header::CONTENT_ENCODING)
+        .is_some_and(|value| value.as_bytes() == b"gzip");
+    let body = if gzipped {
+        let mut decoded = Vec::new();
+        GzDecoder::new(body.as_ref())
+            .read_to_end(&mut decoded)
+            .expect("gzip request body must decode");
+        decoded
+    } else {
+        body.to_vec()
+    };
+    if let Ok(body) = serde_json::from_slice(&body) {
+        state
+            .bodies
+            .lock()
+            .expect("body log mutex must not be poisoned")
+            .push(body);
+    }
 
     let response = {
         let mut queue = state
@@ -61,12 +124,29 @@ async fn pipeline_handler(State(state): State<MockState>, _body: String) -> impl
         tokio::time::sleep(response.delay).await;
     }
 
-    (response.status, Json(response.body))
+    let mut http_response = match (response.content_type, response.body) {
+        (Some(content_type), JsonValue::String(text)) => (
+            response.status,
+            [(header::CONTENT_TYPE, content_type)],
+            text,
+        )
+            .into_response(),
+        (_, body) => (response.status, Json(body)).into_response(),
+    };
+    for (name, value) in response.headers {
+        http_response.headers_mut().insert(
+            header::HeaderName::from_static(name),
+            header::HeaderValue::from_static(value),
+        );
+    }
+    http_response
 }
 
 struct TestServer {
     base_url: String,
     hits: Arc<AtomicUsize>,
+    bodies: Arc<Mutex<Vec<JsonValue>>>,
+    authorizations: Arc<Mutex<Vec<String>>>,
     task: tokio::task::JoinHandle<()>,
 }
 
@@ -80,12 +160,28 @@ impl TestServer {
     fn pipeline_url(&self) -> String {
         format!("{}/v2/pipeline", self.base_url)
     }
+
+    fn bodies(&self) -> Vec<JsonValue> {
+        self.bodies
+            .lock()
+            .expect("body log mutex must not be poisoned")
+            .clone()
+    }
+
+    fn authorizations(&self) -> Vec<String> {
+        self.authorizations
+            .lock()
+            .expect("authorization log mutex must not be poisoned")
+            .clone()
+    }
 }
 
 async fn spawn_server(responses: Vec<MockResponse>) -> TestServer {
     let state = MockState {
         responses: Arc::new(Mutex::new(responses.into())),
         hits: Arc::new(AtomicUsize::new(0)),
+        bodies: Arc::new(Mutex::new(Vec::new())),
+        authorizations: Arc::new(Mutex::new(Vec::new())),
     };
 
     let app = Router::new()
@@ -105,6 +201,8 @@ async fn spawn_server(responses: Vec<MockResponse>) -> TestServer {
     TestServer {
         base_url: format!("http://{address}"),
         hits: state.hits,
+        bodies: state.bodies,
+        authorizations: state.authorizations,
         task,
     }
 }
@@ -141,6 +239,31 @@ fn query_pipeline_body() -> JsonValue {
     })
 }
 
+fn rows_pipeline_body(ids: &[i64]) -> JsonValue {
+    let rows: Vec<JsonValue> = ids
+        .iter()
+        .map(|id| json!([{ "type": "integer", "value": id.to_string() }]))
+        .collect();
+    json!({
+        "results": [
+            {
+                "type": "ok",
+                "response": {
+                    "type": "execute",
+                    "result": {
+                        "cols": [{ "name": "id", "decltype": "INTEGER" }],
+                        "rows": rows
+                    }
+                }
+            },
+            {
+                "type": "ok",
+                "response": { "type": "close" }
+            }
+        ]
+    })
+}
+
 fn execute_pipeline_body(affected_rows: u64, last_insert_rowid: Option<&str>) -> JsonValue {
     json!({
         "results": [
@@ -183,6 +306,92 @@ async fn query_returns_rows_and_cols() {
     assert_eq!(result.rows.len(), 1);
     assert_eq!(result.rows[0][0], Value::Integer(1));
     assert_eq!(result.rows[0][1], Value::Text("Kit".to_owned()));
+    assert!(result.elapsed.is_some());
+    assert_eq!(server.hits.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn compression_sends_gzipped_request_body() {
+    let server = spawn_server(vec![MockResponse::json(
+        StatusCode::OK,
+        query_pipeline_body(),
+    )])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token").with_options(ClientOptions {
+        compression: true,
+        ..ClientOptions::default()
+    });
+
+    let result = db
+        .query("SELECT id, name FROM users", ())
+        .await
+        .expect("query must succeed");
+
+    assert_eq!(result.rows.len(), 1);
+    let bodies = server.bodies();
+    assert_eq!(bodies.len(), 1);
+    assert_eq!(
+        bodies[0]["requests"][0]["stmt"]["sql"],
+        "SELECT id, name FROM users"
+    );
+}
+
+#[tokio::test]
+async fn explain_decodes_query_plan_rows() {
+    let server = spawn_server(vec![MockResponse::json(
+        StatusCode::OK,
+        json!({
+            "results": [
+                {
+                    "type": "ok",
+                    "response": {
+                        "type": "execute",
+                        "result": {
+                            "cols": [
+                                { "name": "id", "decltype": null },
+                                { "name": "parent", "decltype": null },
+                                { "name": "detail", "decltype": null }
+                            ],
+                            "rows": [
+                                [
+                                    { "type": "integer", "value": "3" },
+                                    { "type": "integer", "value": "0" },
+                                    { "type": "text", "value": "SEARCH users USING INTEGER PRIMARY KEY (rowid=?)" }
+                                ]
+                            ]
+                        }
+                    }
+                },
+                { "type": "ok", "response": { "type": "close" } }
+            ]
+        }),
+    )])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let plan = db
+        .explain("SELECT * FROM users WHERE id = ?", [Value::integer(1)])
+        .await
+        .expect("explain must succeed");
+
+    assert_eq!(
+        plan,
+        vec![QueryPlanStep {
+            id: 3,
+            parent: 0,
+            detail: "SEARCH users USING INTEGER PRIMARY KEY (rowid=?)".to_owned(),
+        }]
+    );
+    assert_eq!(
+        server.bodies()[0]["requests"][0]["stmt"]["sql"],
+        "EXPLAIN QUERY PLAN SELECT * FROM users WHERE id = ?"
+    );
+
+    let err = db
+        .explain("explain select 1", ())
+        .await
+        .expect_err("nested EXPLAIN must be rejected");
+    assert!(matches!(err, BunnyDbError::Decode(_)));
     assert_eq!(server.hits.load(Ordering::SeqCst), 1);
 }
 
@@ -203,6 +412,81 @@ async fn execute_returns_affected_row_count_and_last_rowid() {
     assert_eq!(result.affected_row_count, 1);
     assert_eq!(result.last_insert_rowid, Some(42));
     assert_eq!(server.hits.load(Ordering::SeqCst), 1);
+    assert_eq!(server.authorizations(), vec!["token".to_owned()]);
+}
+
+fn batch_pipeline_body(mut results: Vec<JsonValue>) -> JsonValue {
+    results.push(json!({ "type": "ok", "response": { "type": "close" } }));
+    json!({ "results": results })
+}
+
+fn exec_ok_result(affected_rows: u64) -> JsonValue {
+    json!({
+        "type": "ok",
+        "response": {
+            "type": "execute",
+            "result": { "affected_row_count": affected_rows }
+        }
+    })
+}
+
+#[tokio::test]
+async fn batch_strict_returns_outcomes_when_all_succeed() {
+    let body = batch_pipeline_body(vec![exec_ok_result(1), exec_ok_result(2)]);
+    let server = spawn_server(vec![MockResponse::json(StatusCode::OK, body)]).await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let outcomes = db
+        .batch_strict([
+            Statement::execute("INSERT INTO users(name) VALUES ('A')", ()),
+            Statement::execute("UPDATE users SET name = 'B'", ()),
+        ])
+        .await
+        .expect("strict batch must succeed");
+
+    let affected: Vec<u64> = outcomes
+        .iter()
+        .filter_map(|outcome| outcome.as_exec())
+        .map(|exec| exec.affected_row_count)
+        .collect();
+    assert_eq!(affected, [1, 2]);
+}
+
+#[tokio::test]
+async fn batch_strict_fails_on_first_sql_error() {
+    let body = batch_pipeline_body(vec![
+        exec_ok_result(1),
+        json!({
+            "type": "error",
+            "error": { "message": "no such table: missing", "code": "SQLITE_ERROR" }
+        }),
+        exec_ok_result(1),
+    ]);
+    let server = spawn_server(vec![MockResponse::json(StatusCode::OK, body)]).await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let err = db
+        .batch_strict([
+            Statement::execute("INSERT INTO users(name) VALUES ('A')", ()),
+            Statement::execute("DELETE FROM missing", ()),
+            Statement::execute("INSERT INTO users(name) VALUES ('B')", ()),
+        ])
+        .await
+        .expect_err("strict batch must fail");
+
+    match err {
+        BunnyDbError::Pipeline {
+            request_index,
+            message,
+            sql,
+            ..
+        } => {
+            assert_eq!(request_index, 1);
+            assert_eq!(message, "no such table: missing");
+            assert_eq!(sql.as_deref(), Some("DELETE FROM missing"));
+        }
+        _ => panic!("expected pipeline error"),
+    }
 }
 
 #[tokio::test]
@@ -267,6 +551,294 @@ async fn batch_returns_statement_level_sql_error_without_failing_request() {
         }
     ));
     assert!(matches!(outcomes[2], StatementOutcome::Query(_)));
+    match &outcomes[1] {
+        StatementOutcome::SqlError { sql, .. } => {
+            assert_eq!(sql.as_deref(), Some("INSER INTO users(name) VALUES (?)"));
+        }
+        _ => panic!("expected sql error"),
+    }
+}
+
+#[tokio::test]
+async fn batch_indexed_correlates_outcomes_with_statements() {
+    let exec = |rows: u64| {
+        json!({
+            "type": "ok",
+            "response": { "type": "execute", "result": { "affected_row_count": rows } }
+        })
+    };
+    let body = json!({
+        "results": [
+            exec(1),
+            {
+                "type": "error",
+                "error": { "message": "UNIQUE constraint failed: users.name", "code": "SQLITE_CONSTRAINT" }
+            },
+            exec(1),
+            { "type": "ok", "response": { "type": "close" } }
+        ]
+    });
+    let server = spawn_server(vec![MockResponse::json(StatusCode::OK, body)]).await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let names = ["Kit", "Kit", "Lane"];
+    let outcomes = db
+        .batch_indexed(names.iter().map(|name| {
+            Statement::execute("INSERT INTO users (name) VALUES (?)", [Value::text(*name)])
+        }))
+        .await
+        .expect("batch must succeed with per-statement errors");
+
+    let inserted: Vec<(usize, &str)> = outcomes
+        .iter()
+        .filter(|(_, outcome)| outcome.as_exec().is_some())
+        .map(|(index, _)| (*index, names[*index]))
+        .collect();
+    assert_eq!(inserted, [(0, "Kit"), (2, "Lane")]);
+
+    let failed: Vec<usize> = outcomes
+        .iter()
+        .filter_map(|(index, outcome)| match outcome {
+            StatementOutcome::SqlError { request_index, .. } => {
+                assert_eq!(request_index, index);
+                Some(*index)
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(failed, [1]);
+}
+
+#[tokio::test]
+async fn batch_splits_across_requests_above_statement_limit() {
+    let exec = json!({
+        "type": "ok",
+        "response": { "type": "execute", "result": { "affected_row_count": 1 } }
+    });
+    let close = json!({ "type": "ok", "response": { "type": "close" } });
+    let failed = json!({
+        "type": "error",
+        "error": { "message": "UNIQUE constraint failed: users.name", "code": "SQLITE_CONSTRAINT" }
+    });
+    let server = spawn_server(vec![
+        MockResponse::json(
+            StatusCode::OK,
+            json!({ "results": [exec.clone(), exec.clone(), close.clone()] }),
+        ),
+        MockResponse::json(
+            StatusCode::OK,
+            json!({ "results": [exec.clone(), failed, close.clone()] }),
+        ),
+        MockResponse::json(StatusCode::OK, json!({ "results": [exec, close] })),
+    ])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token").with_options(ClientOptions {
+        max_statements_per_request: Some(2),
+        ..ClientOptions::default()
+    });
+
+    let names = ["A", "B", "C", "C", "D"];
+    let outcomes = db
+        .batch(names.iter().map(|name| {
+            Statement::execute("INSERT INTO users (name) VALUES (?)", [Value::text(*name)])
+        }))
+        .await
+        .expect("split batch must succeed with per-statement errors");
+
+    assert_eq!(outcomes.len(), 5);
+    for index in [0, 1, 2, 4] {
+        assert!(outcomes[index].as_exec().is_some(), "outcome {index}");
+    }
+    assert!(matches!(
+        &outcomes[3],
+        StatementOutcome::SqlError {
+            request_index: 3,
+            ..
+        }
+    ));
+
+    let bodies = server.bodies();
+    assert_eq!(bodies.len(), 3);
+    let sent: Vec<Vec<&str>> = bodies
+        .iter()
+        .map(|body| {
+            body["requests"]
+                .as_array()
+                .expect("requests")
+                .iter()
+                .filter(|request| request["type"] == "execute")
+                .map(|request| request["stmt"]["args"][0]["value"].as_str().expect("name"))
+                .collect()
+        })
+        .collect();
+    assert_eq!(sent, [vec!["A", "B"], vec!["C", "C"], vec!["D"]]);
+}
+
+#[tokio::test]
+async fn batch_items_count_store_sql_toward_statement_limit() {
+    let close = json!({ "type": "ok", "response": { "type": "close" } });
+    let server = spawn_server(vec![
+        MockResponse::json(
+            StatusCode::OK,
+            json!({
+                "results": [
+                    { "type": "ok", "response": { "type": "store_sql" } },
+                    exec_ok_result(1),
+                    exec_ok_result(1),
+                    close.clone()
+                ]
+            }),
+        ),
+        MockResponse::json(
+            StatusCode::OK,
+            json!({
+                "results": [
+                    { "type": "ok", "response": { "type": "store_sql" } },
+                    exec_ok_result(1),
+                    exec_ok_result(1),
+                    close
+                ]
+            }),
+        ),
+    ])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token").with_options(ClientOptions {
+        max_statements_per_request: Some(3),
+        ..ClientOptions::default()
+    });
+
+    let insert = db.prepare("INSERT INTO users (name) VALUES (?)");
+    let outcomes = db
+        .batch_items([
+            BatchItem::Prepared(&insert, [Value::text("A")].into()),
+            BatchItem::Prepared(&insert, [Value::text("B")].into()),
+            BatchItem::Adhoc(Statement::execute("DELETE FROM logs", ())),
+            BatchItem::Prepared(&insert, [Value::text("C")].into()),
+        ])
+        .await
+        .expect("batch must succeed");
+    assert_eq!(outcomes.len(), 4);
+
+    let types: Vec<Vec<String>> = server
+        .bodies()
+        .iter()
+        .map(|body| {
+            body["requests"]
+                .as_array()
+                .expect("requests")
+                .iter()
+                .map(|request| request["type"].as_str().unwrap_or_default().to_owned())
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        types,
+        [
+            ["store_sql", "execute", "execute", "close"],
+            ["store_sql", "execute", "execute", "close"],
+        ]
+    );
+}
+
+#[tokio::test]
+async fn atomic_batch_above_statement_limit_is_refused() {
+    let server = spawn_server(Vec::new()).await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token").with_options(ClientOptions {
+        max_statements_per_request: Some(2),
+        ..ClientOptions::default()
+    });
+
+    let err = db
+        .execute_batch_atomic((0..3).map(|_| Statement::execute("DELETE FROM users", ())))
+        .await
+        .expect_err("atomic batch must not be split");
+
+    assert!(matches!(err, BunnyDbError::Config(message) if message.contains("not split")));
+    assert_eq!(server.hits.load(Ordering::SeqCst), 0);
+}
+
+/// Self-signed CA used only to exercise the certificate plumbing.
+const TEST_CA_PEM: &str = "\
+-----BEGIN CERTIFICATE-----
+MIIBjDCCATGgAwIBAgIUFYc32wh5QnoUO0KFg4d/il+peckwCgYIKoZIzj0EAwIw
+GjEYMBYGA1UEAwwPYnVubnlkYi10ZXN0LWNhMCAXDTI2MTAxNzA2MjMyNFoYDzIx
+MjYwOTIzMDYyMzI0WjAaMRgwFgYDVQQDDA9idW5ueWRiLXRlc3QtY2EwWTATBgcq
+hkjOPQIBBggqhkjOPQMBBwNCAAR/XINmvSk+ckl1pfcVHVvqa2XVD6tQfhdeNNvZ
+aECenYAEz9AGOn6Jwbf6uTb0YXx4zGyhz03WMVtow002d62Mo1MwUTAdBgNVHQ4E
+FgQU7gY62MTHeDSdYzqRGHtvhvUaT/cwHwYDVR0jBBgwFoAU7gY62MTHeDSdYzqR
+GHtvhvUaT/cwDwYDVR0TAQH/BAUwAwEB/zAKBggqhkjOPQQDAgNJADBGAiEA/qpn
+S6TYeBuPlhMJcIjgVvOh0vLDsdg4BBjiLSh3rfQCIQCUNh1El/tUart5hAzZzJku
+ZOGqQDe8eWp8IKcc/wmUkg==
+-----END CERTIFICATE-----
+";
+
+#[tokio::test]
+async fn custom_root_certificate_builds_working_client() {
+    let server = spawn_server(vec![MockResponse::json(
+        StatusCode::OK,
+        execute_pipeline_body(1, None),
+    )])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token")
+        .add_root_certificate(TEST_CA_PEM.as_bytes())
+        .expect("valid certificate must be accepted")
+        .danger_accept_invalid_certs(false)
+        .expect("client must build");
+
+    let result = db
+        .execute("DELETE FROM users WHERE id = ?", [Value::integer(1)])
+        .await
+        .expect("request must succeed with rebuilt client");
+    assert_eq!(result.affected_row_count, 1);
+
+    let err = BunnyDbClient::new(server.pipeline_url(), "token")
+        .add_root_certificate(
+            b"-----BEGIN CERTIFICATE-----\nnot base64\n-----END CERTIFICATE-----\n",
+        )
+        .expect_err("garbage certificate must be rejected");
+    assert!(matches!(err, BunnyDbError::Config(_)));
+}
+
+#[cfg(feature = "request-log")]
+#[tokio::test]
+async fn request_log_appends_one_json_line_per_request() {
+    let server = spawn_server(vec![
+        MockResponse::json(StatusCode::OK, execute_pipeline_body(1, None)),
+        MockResponse::json(StatusCode::OK, execute_pipeline_body(1, None)),
+    ])
+    .await;
+    let path =
+        std::env::temp_dir().join(format!("bunnydb-request-log-{}.ndjson", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let db = BunnyDbClient::new(server.pipeline_url(), "secret-token")
+        .with_request_log(&path)
+        .expect("log file must open");
+
+    db.execute("DELETE FROM users WHERE id = ?", [Value::integer(1)])
+        .await
+        .expect("first request must succeed");
+    db.execute("DELETE FROM users WHERE id = ?", [Value::integer(2)])
+        .await
+        .expect("second request must succeed");
+
+    // Dropping the client ends the log; its writer thread then flushes.
+    drop(db);
+    let mut log = String::new();
+    for _ in 0..200 {
+        log = std::fs::read_to_string(&path).expect("log must be readable");
+        if log.lines().count() == 2 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    let _ = std::fs::remove_file(&path);
+    assert!(!log.contains("secret-token"));
+    let lines: Vec<JsonValue> = log
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each line must be JSON"))
+        .collect();
+    assert_eq!(lines, server.bodies());
+    assert_eq!(lines.len(), 2);
 }
 
 #[tokio::test]
@@ -281,6 +853,7 @@ async fn retries_on_retryable_http_status() {
         timeout_ms: 1_000,
         max_retries: 1,
         retry_backoff_ms: 1,
+        ..ClientOptions::default()
     });
 
     let result = db
@@ -293,58 +866,2004 @@ async fn retries_on_retryable_http_status() {
 }
 
 #[tokio::test]
-async fn request_timeout_surfaces_transport_error() {
-    let server = spawn_server(vec![MockResponse::json(
-        StatusCode::OK,
-        execute_pipeline_body(1, Some("1")),
-    )
-    .with_delay(Duration::from_millis(150))])
+async fn set_options_applies_to_next_request() {
+    let server = spawn_server(vec![
+        MockResponse::json(StatusCode::INTERNAL_SERVER_ERROR, json!({"error": "boom"})),
+        MockResponse::json(StatusCode::INTERNAL_SERVER_ERROR, json!({"error": "boom"})),
+        MockResponse::json(StatusCode::OK, execute_pipeline_body(1, None)),
+    ])
     .await;
 
-    let db = BunnyDbClient::new(server.pipeline_url(), "token").with_options(ClientOptions {
-        timeout_ms: 20,
+    let mut db = BunnyDbClient::new(server.pipeline_url(), "token").with_options(ClientOptions {
         max_retries: 0,
+        ..ClientOptions::default()
+    });
+    assert_eq!(db.options().max_retries, 0);
+
+    db.execute("DELETE FROM users", ())
+        .await
+        .expect_err("no retries configured");
+    assert_eq!(server.hits.load(Ordering::SeqCst), 1);
+
+    db.set_options(ClientOptions {
+        max_retries: 2,
         retry_backoff_ms: 1,
+        ..db.options().clone()
     });
+    assert_eq!(db.options().max_retries, 2);
 
-    let err = db
+    let result = db
         .execute("DELETE FROM users", ())
         .await
-        .expect_err("request must timeout");
-
-    match err {
-        BunnyDbError::Transport(inner) => assert!(inner.is_timeout()),
-        _ => panic!("expected transport timeout error"),
-    }
+        .expect("retry enabled by set_options");
+    assert_eq!(result.affected_row_count, 1);
+    assert_eq!(server.hits.load(Ordering::SeqCst), 3);
 }
 
 #[tokio::test]
-async fn query_pipeline_sql_error_in_execute_is_top_level_error() {
-    let body = json!({
-        "results": [
-            {
-                "type": "error",
-                "error": {
-                    "message": "no such table: users",
-                    "code": "SQLITE_ERROR"
-                }
-            },
-            {
-                "type": "ok",
-                "response": { "type": "close" }
-            }
-        ]
+async fn http_error_reports_attempt_count() {
+    let server = spawn_server(vec![
+        MockResponse::json(StatusCode::SERVICE_UNAVAILABLE, json!({"error": "busy"})),
+        MockResponse::json(StatusCode::BAD_GATEWAY, json!({"error": "proxy"})),
+        MockResponse::json(StatusCode::BAD_REQUEST, json!({"error": "bad"})),
+    ])
+    .await;
+
+    let db = BunnyDbClient::new(server.pipeline_url(), "token").with_options(ClientOptions {
+        max_retries: 5,
+        retry_backoff_ms: 1,
+        ..ClientOptions::default()
     });
-    let server = spawn_server(vec![MockResponse::json(StatusCode::OK, body)]).await;
-    let db = BunnyDbClient::new(server.pipeline_url(), "token");
 
     let err = db
-        .query("SELECT * FROM users", ())
+        .execute("DELETE FROM users", ())
         .await
-        .expect_err("query must fail");
+        .expect_err("400 must not be retried");
 
-    match err {
-        BunnyDbError::Pipeline { request_index, .. } => assert_eq!(request_index, 0),
-        _ => panic!("expected pipeline error"),
+    match &err {
+        BunnyDbError::Http {
+            status, attempts, ..
+        } => {
+            assert_eq!(*status, 400);
+            assert_eq!(*attempts, 3);
+        }
+        other => panic!("expected http error, got {other:?}"),
+    }
+    assert!(err
+        .to_string()
+        .starts_with("http error 400 after 3 attempts"));
+}
+
+#[tokio::test]
+async fn structured_error_body_becomes_api_error() {
+    let server = spawn_server(vec![
+        MockResponse::json(
+            StatusCode::BAD_REQUEST,
+            json!({ "message": "no such table: users", "code": "SQLITE_ERROR" }),
+        ),
+        MockResponse::json(StatusCode::BAD_REQUEST, json!({ "error": "bad" })),
+    ])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let err = db
+        .execute("DELETE FROM users", ())
+        .await
+        .expect_err("400 must fail");
+    match &err {
+        BunnyDbError::Api {
+            status,
+            message,
+            code,
+            attempts,
+        } => {
+            assert_eq!(*status, 400);
+            assert_eq!(message, "no such table: users");
+            assert_eq!(code.as_deref(), Some("SQLITE_ERROR"));
+            assert_eq!(*attempts, 1);
+        }
+        other => panic!("expected api error, got {other:?}"),
+    }
+    assert_eq!(
+        err.to_string(),
+        "api error 400: no such table: users (SQLITE_ERROR)"
+    );
+
+    let err = db
+        .execute("DELETE FROM users", ())
+        .await
+        .expect_err("400 must fail");
+    assert!(
+        matches!(err, BunnyDbError::Http { status: 400, ref body, .. } if body.contains("bad"))
+    );
+}
+
+#[tokio::test]
+async fn total_deadline_stops_retrying_early() {
+    let server = spawn_server(
+        (0..10)
+            .map(|_| MockResponse::json(StatusCode::SERVICE_UNAVAILABLE, json!({"error": "busy"})))
+            .collect(),
+    )
+    .await;
+
+    // Backoff doubles from 20ms, so the third retry (80ms) cannot start
+    // before the 100ms deadline even though nine retries remain.
+    let db = BunnyDbClient::new(server.pipeline_url(), "token").with_options(ClientOptions {
+        max_retries: 9,
+        retry_backoff_ms: 20,
+        total_deadline_ms: Some(100),
+        ..ClientOptions::default()
+    });
+
+    let started = std::time::Instant::now();
+    let err = db
+        .execute("DELETE FROM users", ())
+        .await
+        .expect_err("request must fail once the deadline is reached");
+
+    assert!(matches!(err, BunnyDbError::Http { status: 503, .. }));
+    assert!(server.hits.load(Ordering::SeqCst) < 10);
+    assert!(started.elapsed() < Duration::from_millis(500));
+}
+
+#[tokio::test]
+async fn total_deadline_clamps_attempt_timeout() {
+    let server = spawn_server(vec![MockResponse::json(
+        StatusCode::OK,
+        execute_pipeline_body(1, None),
+    )
+    .with_delay(Duration::from_secs(5))])
+    .await;
+
+    let db = BunnyDbClient::new(server.pipeline_url(), "token").with_options(ClientOptions {
+        timeout_ms: 10_000,
+        total_deadline_ms: Some(100),
+        ..ClientOptions::default()
+    });
+
+    let started = std::time::Instant::now();
+    let err = db
+        .execute("DELETE FROM users", ())
+        .await
+        .expect_err("attempt must time out at the deadline");
+
+    assert!(matches!(err, BunnyDbError::Transport { ref source, .. } if source.is_timeout()));
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn on_request_reports_every_attempt() {
+    let server = spawn_server(vec![
+        MockResponse::json(StatusCode::SERVICE_UNAVAILABLE, json!({"error": "busy"})),
+        MockResponse::json(StatusCode::OK, execute_pipeline_body(1, None)),
+    ])
+    .await;
+
+    let metrics = Arc::new(Mutex::new(Vec::<RequestMetrics>::new()));
+    let sink = metrics.clone();
+    let db = BunnyDbClient::new(server.pipeline_url(), "token").with_options(ClientOptions {
+        max_retries: 1,
+        retry_backoff_ms: 1,
+        on_request: Some(Arc::new(move |attempt: &RequestMetrics| {
+            sink.lock()
+                .expect("metrics mutex must not be poisoned")
+                .push(attempt.clone());
+        })),
+        ..ClientOptions::default()
+    });
+
+    db.execute("DELETE FROM users", ())
+        .await
+        .expect("request must succeed after retry");
+
+    let metrics = metrics
+        .lock()
+        .expect("metrics mutex must not be poisoned")
+        .clone();
+    assert_eq!(metrics.len(), 2);
+    assert_eq!(metrics[0].attempt, 0);
+    assert_eq!(metrics[0].status, Some(503));
+    assert!(metrics[0].retried);
+    assert_eq!(metrics[1].attempt, 1);
+    assert_eq!(metrics[1].status, Some(200));
+    assert!(!metrics[1].retried);
+    assert!(metrics.iter().all(|attempt| attempt.bytes_sent > 0));
+}
+
+#[tokio::test]
+async fn on_request_reports_request_and_response_sizes() {
+    let busy = json!({"error": "busy"});
+    let ok = execute_pipeline_body(1, None);
+    let server = spawn_server(vec![
+        MockResponse::json(StatusCode::SERVICE_UNAVAILABLE, busy.clone()),
+        MockResponse::json(StatusCode::OK, ok.clone()),
+    ])
+    .await;
+
+    let metrics = Arc::new(Mutex::new(Vec::<RequestMetrics>::new()));
+    let sink = metrics.clone();
+    let db = BunnyDbClient::new(server.pipeline_url(), "token").with_options(ClientOptions {
+        max_retries: 1,
+        retry_backoff_ms: 1,
+        on_request: Some(Arc::new(move |attempt: &RequestMetrics| {
+            sink.lock()
+                .expect("metrics mutex must not be poisoned")
+                .push(attempt.clone());
+        })),
+        ..ClientOptions::default()
+    });
+
+    db.execute("DELETE FROM users", ())
+        .await
+        .expect("request must succeed after retry");
+
+    let metrics = metrics
+        .lock()
+        .expect("metrics mutex must not be poisoned")
+        .clone();
+    let bodies = server.bodies();
+    let request_bytes = serde_json::to_vec(&bodies[0]).expect("json").len();
+    let busy_bytes = serde_json::to_vec(&busy).expect("json").len();
+    let ok_bytes = serde_json::to_vec(&ok).expect("json").len();
+
+    assert_eq!(metrics.len(), 2);
+    assert_eq!(metrics[0].bytes_sent, request_bytes);
+    assert_eq!(metrics[0].bytes_received, busy_bytes);
+    assert_eq!(
+        metrics[0].total,
+        RequestSize {
+            request_bytes,
+            response_bytes: busy_bytes,
+        }
+    );
+    assert_eq!(metrics[1].bytes_received, ok_bytes);
+    assert_eq!(
+        metrics[1].total,
+        RequestSize {
+            request_bytes: 2 * request_bytes,
+            response_bytes: busy_bytes + ok_bytes,
+        }
+    );
+}
+
+#[tokio::test]
+async fn circuit_breaker_opens_after_consecutive_failures() {
+    let unavailable =
+        || MockResponse::json(StatusCode::SERVICE_UNAVAILABLE, json!({"error": "down"}));
+    let server = spawn_server(vec![unavailable(), unavailable(), unavailable()]).await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token").with_options(ClientOptions {
+        breaker_threshold: 2,
+        breaker_cooldown_ms: 50,
+        ..ClientOptions::default()
+    });
+
+    for _ in 0..2 {
+        let err = db.execute("DELETE FROM users", ()).await.expect_err("503");
+        assert!(matches!(err, BunnyDbError::Http { status: 503, .. }));
+    }
+
+    // Clones share breaker state.
+    let err = db
+        .clone()
+        .execute("DELETE FROM users", ())
+        .await
+        .expect_err("breaker must be open");
+    assert!(matches!(err, BunnyDbError::CircuitOpen { .. }));
+    assert_eq!(server.hits.load(Ordering::SeqCst), 2);
+
+    // After the cooldown a single probe goes through; its failure reopens.
+    tokio::time::sleep(Duration::from_millis(70)).await;
+    let err = db.execute("DELETE FROM users", ()).await.expect_err("503");
+    assert!(matches!(err, BunnyDbError::Http { status: 503, .. }));
+    assert_eq!(server.hits.load(Ordering::SeqCst), 3);
+
+    let err = db
+        .execute("DELETE FROM users", ())
+        .await
+        .expect_err("breaker must reopen");
+    assert!(matches!(err, BunnyDbError::CircuitOpen { .. }));
+    assert_eq!(server.hits.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn query_surfaces_baton_and_base_url() {
+    let mut body = query_pipeline_body();
+    body["baton"] = json!("baton-123");
+    body["base_url"] = json!("https://replica.example.com");
+    let server = spawn_server(vec![MockResponse::json(StatusCode::OK, body)]).await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let result = db
+        .query("SELECT id, name FROM users", ())
+        .await
+        .expect("query must succeed");
+
+    assert_eq!(result.baton.as_deref(), Some("baton-123"));
+    assert_eq!(
+        result.base_url.as_deref(),
+        Some("https://replica.example.com")
+    );
+}
+
+#[tokio::test]
+async fn on_request_surfaces_allowlisted_response_headers() {
+    let server = spawn_server(vec![MockResponse::json(
+        StatusCode::OK,
+        execute_pipeline_body(1, None),
+    )
+    .with_header("x-ratelimit-remaining", "41")
+    .with_header("x-request-id", "req-7")
+    .with_header("set-cookie", "session=secret")])
+    .await;
+
+    let metrics = Arc::new(Mutex::new(Vec::<RequestMetrics>::new()));
+    let sink = metrics.clone();
+    let db = BunnyDbClient::new(server.pipeline_url(), "token").with_options(ClientOptions {
+        capture_headers: vec![
+            "X-RateLimit-Remaining".to_owned(),
+            "x-missing".to_owned(),
+            "Set-Cookie".to_owned(),
+        ],
+        on_request: Some(Arc::new(move |attempt: &RequestMetrics| {
+            sink.lock()
+                .expect("metrics mutex must not be poisoned")
+                .push(attempt.clone());
+        })),
+        ..ClientOptions::default()
+    });
+
+    db.execute("DELETE FROM users", ())
+        .await
+        .expect("request must succeed");
+
+    let metrics = metrics
+        .lock()
+        .expect("metrics mutex must not be poisoned")
+        .clone();
+    let headers = metrics[0]
+        .response_headers
+        .as_ref()
+        .expect("headers must be captured");
+    assert_eq!(headers.len(), 1);
+    assert_eq!(
+        headers.get("x-ratelimit-remaining").map(String::as_str),
+        Some("41")
+    );
+}
+
+#[tokio::test]
+async fn in_stream_calls_keep_stream_open_until_closed() {
+    let open_body = |body: JsonValue, baton: &str| {
+        let mut body = body;
+        body["results"]
+            .as_array_mut()
+            .expect("results must be an array")
+            .pop();
+        body["baton"] = json!(baton);
+        body
+    };
+    let server = spawn_server(vec![
+        MockResponse::json(
+            StatusCode::OK,
+            open_body(execute_pipeline_body(1, None), "baton-1"),
+        ),
+        MockResponse::json(StatusCode::OK, open_body(query_pipeline_body(), "baton-2")),
+        MockResponse::json(
+            StatusCode::OK,
+            json!({ "results": [{ "type": "ok", "response": { "type": "close" } }] }),
+        ),
+        MockResponse::json(StatusCode::OK, execute_pipeline_body(1, None)),
+    ])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let result = db
+        .execute_in_stream(None, "PRAGMA foreign_keys = ON", ())
+        .await
+        .expect("execute without close must succeed");
+    assert_eq!(result.baton.as_deref(), Some("baton-1"));
+
+    let rows = db
+        .query_in_stream(result.baton.as_deref(), "SELECT id, name FROM users", ())
+        .await
+        .expect("query on the open stream must succeed");
+    assert_eq!(rows.row_count(), 1);
+    assert_eq!(rows.baton.as_deref(), Some("baton-2"));
+
+    db.close_stream("baton-2")
+        .await
+        .expect("close must succeed");
+    db.execute("DELETE FROM users WHERE id = 2", ())
+        .await
+        .expect("plain execute must succeed");
+
+    let bodies = server.bodies();
+    let request_types = |body: &JsonValue| -> Vec<JsonValue> {
+        body["requests"]
+            .as_array()
+            .expect("requests must be an array")
+            .iter()
+            .map(|request| request["type"].clone())
+            .collect()
+    };
+    assert_eq!(request_types(&bodies[0]), ["execute"]);
+    assert_eq!(bodies[0]["baton"], JsonValue::Null);
+    assert_eq!(request_types(&bodies[1]), ["execute"]);
+    assert_eq!(bodies[1]["baton"], "baton-1");
+    assert_eq!(request_types(&bodies[2]), ["close"]);
+    assert_eq!(bodies[2]["baton"], "baton-2");
+    assert_eq!(request_types(&bodies[3]), ["execute", "close"]);
+    assert_eq!(bodies[3]["baton"], JsonValue::Null);
+}
+
+#[tokio::test]
+async fn elapsed_covers_network_time() {
+    let server = spawn_server(vec![MockResponse::json(
+        StatusCode::OK,
+        execute_pipeline_body(1, None),
+    )
+    .with_delay(Duration::from_millis(30))])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let result = db
+        .execute("DELETE FROM users", ())
+        .await
+        .expect("execute must succeed");
+
+    let elapsed = result.elapsed.expect("elapsed must be set");
+    assert!(elapsed >= Duration::from_millis(30), "elapsed: {elapsed:?}");
+}
+
+#[tokio::test]
+async fn cancelling_aborts_in_flight_request() {
+    let server = spawn_server(vec![MockResponse::json(
+        StatusCode::OK,
+        query_pipeline_body(),
+    )
+    .with_delay(Duration::from_secs(5))])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+    let cancel = CancellationToken::new();
+
+    let trigger = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        trigger.cancel();
+    });
+
+    let started = std::time::Instant::now();
+    let err = db
+        .query_cancellable("SELECT id, name FROM users", (), &cancel)
+        .await
+        .expect_err("request must be cancelled");
+
+    assert!(matches!(err, BunnyDbError::Cancelled));
+    assert!(started.elapsed() < Duration::from_secs(2));
+    assert_eq!(server.hits.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn html_success_body_is_reported_as_non_json() {
+    let page = format!("<html><body>{}</body></html>", "Bad gateway ".repeat(100));
+    let server = spawn_server(vec![MockResponse::text(
+        StatusCode::OK,
+        "text/html; charset=utf-8",
+        &page,
+    )])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let err = db
+        .query("SELECT 1", ())
+        .await
+        .expect_err("html body must fail");
+
+    match &err {
+        BunnyDbError::NonJsonResponse {
+            status,
+            content_type,
+            snippet,
+            body,
+        } => {
+            assert_eq!(*status, 200);
+            assert_eq!(content_type.as_deref(), Some("text/html; charset=utf-8"));
+            assert!(snippet.starts_with("<html><body>Bad gateway"));
+            assert!(snippet.chars().count() <= 203);
+            assert_eq!(body, &page);
+        }
+        other => panic!("expected non-JSON response error, got {other:?}"),
+    }
+    assert!(err.to_string().len() < 300);
+}
+
+#[tokio::test]
+async fn oversized_request_is_rejected_before_sending() {
+    let server = spawn_server(Vec::new()).await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token").with_options(ClientOptions {
+        max_request_bytes: Some(256),
+        ..ClientOptions::default()
+    });
+
+    let err = db
+        .batch((0..20).map(|id| {
+            Statement::execute(
+                "INSERT INTO \"users\" (\"id\", \"name\") VALUES (?, ?)",
+                [Value::integer(id), Value::text("Kit")],
+            )
+        }))
+        .await
+        .expect_err("oversized batch must fail");
+
+    match err {
+        BunnyDbError::RequestTooLarge { size, limit } => {
+            assert_eq!(limit, 256);
+            assert!(size > limit);
+        }
+        other => panic!("expected request-too-large error, got {other:?}"),
+    }
+    assert_eq!(server.hits.load(Ordering::SeqCst), 0);
+}
+
+#[cfg(feature = "blocking")]
+#[tokio::test]
+async fn blocking_client_runs_query_execute_and_batch() {
+    use bunnydb_http::blocking::BlockingBunnyDbClient;
+
+    let server = spawn_server(vec![
+        MockResponse::json(StatusCode::OK, query_pipeline_body()),
+        MockResponse::json(StatusCode::OK, execute_pipeline_body(1, Some("5"))),
+        MockResponse::json(StatusCode::OK, batch_pipeline_body(vec![exec_ok_result(2)])),
+    ])
+    .await;
+    let url = server.pipeline_url();
+
+    // The blocking client owns its own runtime, so it runs on a plain thread
+    // while this test's runtime keeps serving the mock.
+    let worker = std::thread::spawn(move || {
+        let db = BlockingBunnyDbClient::new(BunnyDbClient::new(url, "token"))
+            .expect("runtime must start");
+
+        let rows = db
+            .query("SELECT id, name FROM users", ())
+            .expect("query must succeed")
+            .rows
+            .len();
+        let rowid = db
+            .execute("INSERT INTO users (name) VALUES ('Kit')", ())
+            .expect("execute must succeed")
+            .last_insert_rowid;
+        let outcomes = db
+            .batch([Statement::execute("DELETE FROM users", ())])
+            .expect("batch must succeed");
+        (rows, rowid, outcomes.len())
+    });
+    let (rows, rowid, outcomes) = tokio::task::spawn_blocking(move || worker.join())
+        .await
+        .expect("join task must not panic")
+        .expect("worker must not panic");
+
+    assert_eq!(rows, 1);
+    assert_eq!(rowid, Some(5));
+    assert_eq!(outcomes, 1);
+    assert_eq!(server.hits.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn request_timeout_surfaces_transport_error() {
+    let server = spawn_server(vec![MockResponse::json(
+        StatusCode::OK,
+        execute_pipeline_body(1, Some("1")),
+    )
+    .with_delay(Duration::from_millis(150))])
+    .await;
+
+    let db = BunnyDbClient::new(server.pipeline_url(), "token").with_options(ClientOptions {
+        timeout_ms: 20,
+        max_retries: 0,
+        retry_backoff_ms: 1,
+        ..ClientOptions::default()
+    });
+
+    let err = db
+        .execute("DELETE FROM users", ())
+        .await
+        .expect_err("request must timeout");
+
+    match err {
+        BunnyDbError::Transport { source, attempts } => {
+            assert!(source.is_timeout());
+            assert_eq!(attempts, 1);
+        }
+        _ => panic!("expected transport timeout error"),
+    }
+}
+
+#[tokio::test]
+async fn request_timeout_covers_slow_body() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    // Raw server: headers arrive immediately, the body never completes.
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("must bind test listener");
+    let address = listener.local_addr().expect("must have local addr");
+    let task = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.expect("must accept");
+        let mut buffer = [0u8; 4096];
+        let _ = socket.read(&mut buffer).await;
+        socket
+            .write_all(
+                b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 1000\r\n\r\n{\"results\":",
+            )
+            .await
+            .expect("must write headers");
+        tokio::time::sleep(Duration::from_secs(5)).await;
+    });
+
+    let db = BunnyDbClient::new(format!("http://{address}/v2/pipeline"), "token").with_options(
+        ClientOptions {
+            timeout_ms: 200,
+            ..ClientOptions::default()
+        },
+    );
+
+    let started = std::time::Instant::now();
+    let err = db
+        .execute("DELETE FROM users", ())
+        .await
+        .expect_err("slow body must time out");
+    task.abort();
+
+    assert!(matches!(err, BunnyDbError::Transport { ref source, .. } if source.is_timeout()));
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn query_pipeline_sql_error_in_execute_is_top_level_error() {
+    let body = json!({
+        "results": [
+            {
+                "type": "error",
+                "error": {
+                    "message": "no such table: users",
+                    "code": "SQLITE_ERROR"
+                }
+            },
+            {
+                "type": "ok",
+                "response": { "type": "close" }
+            }
+        ]
+    });
+    let server = spawn_server(vec![MockResponse::json(StatusCode::OK, body)]).await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let err = db
+        .query("SELECT * FROM users", ())
+        .await
+        .expect_err("query must fail");
+
+    match err {
+        BunnyDbError::Pipeline {
+            request_index, sql, ..
+        } => {
+            assert_eq!(request_index, 0);
+            assert_eq!(sql.as_deref(), Some("SELECT * FROM users"));
+        }
+        _ => panic!("expected pipeline error"),
+    }
+}
+
+#[tokio::test]
+async fn query_paged_stops_after_short_page() {
+    let server = spawn_server(vec![
+        MockResponse::json(StatusCode::OK, rows_pipeline_body(&[1, 2])),
+        MockResponse::json(StatusCode::OK, rows_pipeline_body(&[3])),
+    ])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let mut pager = db
+        .query_paged("SELECT id FROM users ORDER BY id", (), 2)
+        .expect("pager must build");
+    let mut ids = Vec::new();
+    while let Some(page) = pager.next_page().await.expect("page must load") {
+        ids.extend(page.rows.into_iter().map(|row| row[0].clone()));
+    }
+
+    assert_eq!(
+        ids,
+        vec![Value::Integer(1), Value::Integer(2), Value::Integer(3)]
+    );
+    assert_eq!(server.hits.load(Ordering::SeqCst), 2);
+
+    let bodies = server.bodies();
+    let stmt = &bodies[1]["requests"][0]["stmt"];
+    assert_eq!(
+        stmt["sql"],
+        "SELECT id FROM users ORDER BY id LIMIT ? OFFSET ?"
+    );
+    assert_eq!(stmt["args"][1], json!({ "type": "integer", "value": "2" }));
+}
+
+#[tokio::test]
+async fn query_stream_yields_same_rows_as_query() {
+    use futures_util::TryStreamExt;
+
+    let server = spawn_server(vec![
+        MockResponse::json(StatusCode::OK, rows_pipeline_body(&[1, 2, 3])),
+        MockResponse::json(StatusCode::OK, rows_pipeline_body(&[1, 2])),
+        MockResponse::json(StatusCode::OK, rows_pipeline_body(&[3])),
+    ])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let all = db
+        .query("SELECT id FROM users ORDER BY id", ())
+        .await
+        .expect("query must succeed");
+    let streamed: Vec<Vec<Value>> = db
+        .query_stream("SELECT id FROM users ORDER BY id", (), 2)
+        .try_collect()
+        .await
+        .expect("stream must succeed");
+
+    assert_eq!(streamed, all.rows);
+    assert_eq!(server.hits.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn query_stream_surfaces_errors_as_items() {
+    use futures_util::StreamExt;
+
+    let server = spawn_server(vec![
+        MockResponse::json(StatusCode::OK, rows_pipeline_body(&[1, 2])),
+        MockResponse::json(StatusCode::BAD_REQUEST, json!({"error": "bad"})),
+    ])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let items: Vec<_> = db
+        .query_stream("SELECT id FROM users ORDER BY id", (), 2)
+        .collect()
+        .await;
+    assert_eq!(items.len(), 3);
+    assert!(items[0].is_ok() && items[1].is_ok());
+    assert!(matches!(
+        items[2],
+        Err(BunnyDbError::Http { status: 400, .. })
+    ));
+
+    let items: Vec<_> = db
+        .query_stream("SELECT id FROM users LIMIT 5", (), 2)
+        .collect()
+        .await;
+    assert!(matches!(items.as_slice(), [Err(BunnyDbError::Decode(_))]));
+}
+
+fn atomic_batch_body(step_results: Vec<JsonValue>, step_errors: Vec<JsonValue>) -> JsonValue {
+    json!({
+        "results": [
+            {
+                "type": "ok",
+                "response": {
+                    "type": "batch",
+                    "result": {
+                        "step_results": step_results,
+                        "step_errors": step_errors
+                    }
+                }
+            },
+            {
+                "type": "ok",
+                "response": { "type": "close" }
+            }
+        ]
+    })
+}
+
+#[tokio::test]
+async fn execute_guarded_refuses_delete_without_where() {
+    let server = spawn_server(vec![MockResponse::json(
+        StatusCode::OK,
+        execute_pipeline_body(1, None),
+    )])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let err = db
+        .execute_guarded("DELETE FROM users -- WHERE id = ?", ())
+        .await
+        .expect_err("unfiltered delete must be refused");
+    assert!(matches!(err, BunnyDbError::Decode(message) if message.contains("without WHERE")));
+    assert_eq!(server.hits.load(Ordering::SeqCst), 0);
+
+    let result = db
+        .execute_guarded("delete from users where id = ?", [Value::integer(7)])
+        .await
+        .expect("filtered delete must run");
+    assert_eq!(result.affected_row_count, 1);
+
+    let bodies = server.bodies();
+    assert_eq!(bodies.len(), 1);
+    assert_eq!(
+        bodies[0]["requests"][0]["stmt"]["sql"],
+        "delete from users where id = ?"
+    );
+}
+
+#[tokio::test]
+async fn execute_returning_requests_and_decodes_rows() {
+    let body = json!({
+        "results": [
+            {
+                "type": "ok",
+                "response": {
+                    "type": "execute",
+                    "result": {
+                        "cols": [
+                            { "name": "id", "decltype": "INTEGER" },
+                            { "name": "name", "decltype": "TEXT" },
+                            { "name": "created_at", "decltype": "TEXT" }
+                        ],
+                        "rows": [[
+                            { "type": "integer", "value": "42" },
+                            { "type": "text", "value": "Kit" },
+                            { "type": "text", "value": "2026-01-01 00:00:00" }
+                        ]],
+                        "affected_row_count": 1,
+                        "last_insert_rowid": "42"
+                    }
+                }
+            },
+            { "type": "ok", "response": { "type": "close" } }
+        ]
+    });
+    let server = spawn_server(vec![MockResponse::json(StatusCode::OK, body)]).await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let err = db
+        .execute_returning("INSERT INTO users (name) VALUES (?)", [Value::text("Kit")])
+        .await
+        .expect_err("missing RETURNING must be rejected");
+    assert!(matches!(err, BunnyDbError::Decode(message) if message.contains("RETURNING")));
+
+    let result = db
+        .execute_returning(
+            "INSERT INTO users (name) VALUES (?) RETURNING id, name, created_at",
+            [Value::text("Kit")],
+        )
+        .await
+        .expect("insert returning must succeed");
+    assert_eq!(result.row_count(), 1);
+    assert_eq!(
+        result.rows[0],
+        [
+            Value::integer(42),
+            Value::text("Kit"),
+            Value::text("2026-01-01 00:00:00")
+        ]
+    );
+
+    let bodies = server.bodies();
+    assert_eq!(bodies.len(), 1, "rejected statement must not be sent");
+    assert_eq!(bodies[0]["requests"][0]["stmt"]["want_rows"], true);
+}
+
+#[tokio::test]
+async fn execute_ddl_sends_schema_statement_without_args() {
+    let server = spawn_server(vec![MockResponse::json(
+        StatusCode::OK,
+        execute_pipeline_body(0, None),
+    )])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let result = db
+        .execute_ddl("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)")
+        .await
+        .expect("ddl must succeed");
+    assert!(result.is_ddl_like());
+
+    let err = db
+        .execute_ddl("CREATE TABLE t (a TEXT DEFAULT ?)")
+        .await
+        .expect_err("parameterized ddl must be rejected");
+    assert!(matches!(err, BunnyDbError::Decode(_)));
+
+    let bodies = server.bodies();
+    assert_eq!(bodies.len(), 1, "rejected ddl must not be sent");
+    let stmt = &bodies[0]["requests"][0]["stmt"];
+    assert_eq!(
+        stmt["sql"],
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)"
+    );
+    assert!(stmt.get("args").is_none());
+    assert_eq!(stmt["want_rows"], false);
+}
+
+fn scalar_pipeline_body(name: &str, value: JsonValue) -> JsonValue {
+    json!({
+        "results": [
+            {
+                "type": "ok",
+                "response": {
+                    "type": "execute",
+                    "result": {
+                        "cols": [{ "name": name, "decltype": null }],
+                        "rows": [[value]]
+                    }
+                }
+            },
+            { "type": "ok", "response": { "type": "close" } }
+        ]
+    })
+}
+
+#[tokio::test]
+async fn pragma_helpers_send_pragmas_and_decode_scalars() {
+    let server = spawn_server(vec![
+        MockResponse::json(StatusCode::OK, execute_pipeline_body(0, None)),
+        MockResponse::json(
+            StatusCode::OK,
+            scalar_pipeline_body("user_version", json!({ "type": "integer", "value": "3" })),
+        ),
+        MockResponse::json(
+            StatusCode::OK,
+            json!({
+                "results": [
+                    exec_ok_result(0),
+                    exec_ok_result(1),
+                    { "type": "ok", "response": { "type": "close" } }
+                ]
+            }),
+        ),
+        MockResponse::json(
+            StatusCode::OK,
+            scalar_pipeline_body("journal_mode", json!({ "type": "text", "value": "wal" })),
+        ),
+    ])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    db.set_user_version(3)
+        .await
+        .expect("set_user_version must succeed");
+    assert_eq!(
+        db.get_user_version()
+            .await
+            .expect("get_user_version must succeed"),
+        3
+    );
+    db.batch([
+        Statement::foreign_keys(true),
+        Statement::execute("DELETE FROM orgs WHERE id = 1", ()),
+    ])
+    .await
+    .expect("batch must succeed");
+    assert_eq!(
+        db.journal_mode().await.expect("journal_mode must succeed"),
+        "wal"
+    );
+
+    let sqls: Vec<JsonValue> = server
+        .bodies()
+        .iter()
+        .map(|body| body["requests"][0]["stmt"]["sql"].clone())
+        .collect();
+    assert_eq!(
+        sqls,
+        [
+            "PRAGMA user_version = 3",
+            "PRAGMA user_version",
+            "PRAGMA foreign_keys = ON",
+            "PRAGMA journal_mode",
+        ]
+    );
+    assert_eq!(
+        server.bodies()[2]["requests"][1]["stmt"]["sql"],
+        "DELETE FROM orgs WHERE id = 1"
+    );
+}
+
+#[tokio::test]
+async fn upsert_sends_on_conflict_statement() {
+    let server = spawn_server(vec![
+        MockResponse::json(StatusCode::OK, execute_pipeline_body(1, Some("1"))),
+        // An update on conflict still reports one affected row.
+        MockResponse::json(StatusCode::OK, execute_pipeline_body(1, Some("1"))),
+    ])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    for name in ["Kit", "Kit Renamed"] {
+        let result = db
+            .upsert(
+                "users",
+                &["email"],
+                &[
+                    ("email", Value::text("kit@example.com")),
+                    ("name", Value::text(name)),
+                ],
+            )
+            .await
+            .expect("upsert must succeed");
+        assert_eq!(result.affected_row_count, 1);
+        assert_eq!(result.last_insert_rowid, Some(1));
+    }
+
+    let bodies = server.bodies();
+    assert_eq!(bodies.len(), 2);
+    for body in &bodies {
+        assert_eq!(
+            body["requests"][0]["stmt"]["sql"],
+            "INSERT INTO \"users\" (\"email\", \"name\") VALUES (?, ?) \
+             ON CONFLICT (\"email\") DO UPDATE SET \"name\" = excluded.\"name\""
+        );
+    }
+    assert_eq!(
+        bodies[1]["requests"][0]["stmt"]["args"][1]["value"],
+        "Kit Renamed"
+    );
+}
+
+#[tokio::test]
+async fn insert_many_sends_chunks_in_one_atomic_batch() {
+    let exec = |rows: u64| json!({ "affected_row_count": rows });
+    let body = atomic_batch_body(
+        vec![exec(0), exec(2), exec(2), exec(1), exec(0), JsonValue::Null],
+        vec![JsonValue::Null; 6],
+    );
+    let server = spawn_server(vec![MockResponse::json(StatusCode::OK, body)]).await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let rows: Vec<Vec<Value>> = (1..=5)
+        .map(|id| vec![Value::integer(id), Value::text(format!("user-{id}"))])
+        .collect();
+    let inserted = db
+        .insert_many("users", &["id", "name"], rows, 2)
+        .await
+        .expect("insert must succeed");
+
+    assert_eq!(inserted, 5);
+    assert_eq!(server.hits.load(Ordering::SeqCst), 1);
+
+    let bodies = server.bodies();
+    let steps = bodies[0]["requests"][0]["batch"]["steps"]
+        .as_array()
+        .expect("batch steps must be present")
+        .clone();
+    let sqls: Vec<&str> = steps
+        .iter()
+        .map(|step| step["stmt"]["sql"].as_str().unwrap_or_default())
+        .collect();
+    assert_eq!(
+        sqls,
+        [
+            "BEGIN",
+            "INSERT INTO \"users\" (\"id\", \"name\") VALUES (?, ?), (?, ?)",
+            "INSERT INTO \"users\" (\"id\", \"name\") VALUES (?, ?), (?, ?)",
+            "INSERT INTO \"users\" (\"id\", \"name\") VALUES (?, ?)",
+            "COMMIT",
+            "ROLLBACK",
+        ]
+    );
+    assert_eq!(steps[3]["condition"], json!({ "type": "ok", "step": 2 }));
+}
+
+#[tokio::test]
+async fn execute_script_runs_split_statements_atomically() {
+    let exec = |rows: u64| json!({ "affected_row_count": rows });
+    let body = atomic_batch_body(
+        vec![exec(0), exec(0), exec(1), exec(0), JsonValue::Null],
+        vec![JsonValue::Null; 5],
+    );
+    let server = spawn_server(vec![MockResponse::json(StatusCode::OK, body)]).await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let outcomes = db
+        .execute_script("CREATE TABLE t (v TEXT);\nINSERT INTO t VALUES ('a;b');\n")
+        .await
+        .expect("script must succeed");
+
+    assert_eq!(outcomes.len(), 2);
+    let steps = server.bodies()[0]["requests"][0]["batch"]["steps"].clone();
+    let sqls: Vec<&str> = steps
+        .as_array()
+        .expect("steps must be an array")
+        .iter()
+        .map(|step| step["stmt"]["sql"].as_str().expect("sql must be text"))
+        .collect();
+    assert_eq!(
+        sqls,
+        [
+            "BEGIN",
+            "CREATE TABLE t (v TEXT)",
+            "INSERT INTO t VALUES ('a;b')",
+            "COMMIT",
+            "ROLLBACK"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn execute_batch_summary_aggregates_inserts() {
+    let exec = |rowid: &str| json!({ "affected_row_count": 1, "last_insert_rowid": rowid });
+    let body = atomic_batch_body(
+        vec![
+            json!({ "affected_row_count": 0 }),
+            exec("10"),
+            exec("11"),
+            exec("12"),
+            json!({ "affected_row_count": 0 }),
+            JsonValue::Null,
+        ],
+        vec![JsonValue::Null; 6],
+    );
+    let server = spawn_server(vec![MockResponse::json(StatusCode::OK, body)]).await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let summary = db
+        .execute_batch_summary(["A", "B", "C"].map(|name| {
+            Statement::execute("INSERT INTO users(name) VALUES (?)", [Value::text(name)])
+        }))
+        .await
+        .expect("batch must succeed");
+
+    assert_eq!(
+        summary,
+        BatchSummary {
+            total_affected: 3,
+            last_insert_rowid: Some(12),
+            statement_count: 3,
+        }
+    );
+}
+
+#[tokio::test]
+async fn execute_batch_atomic_reports_failing_statement() {
+    let body = atomic_batch_body(
+        vec![
+            json!({ "affected_row_count": 0 }),
+            json!({ "affected_row_count": 1 }),
+            JsonValue::Null,
+            JsonValue::Null,
+            json!({ "affected_row_count": 0 }),
+        ],
+        vec![
+            JsonValue::Null,
+            JsonValue::Null,
+            json!({ "message": "UNIQUE constraint failed: users.id", "code": "SQLITE_CONSTRAINT" }),
+            JsonValue::Null,
+            JsonValue::Null,
+        ],
+    );
+    let server = spawn_server(vec![MockResponse::json(StatusCode::OK, body)]).await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let err = db
+        .execute_batch_atomic([
+            Statement::execute("INSERT INTO users (id) VALUES (1)", ()),
+            Statement::execute("INSERT INTO users (id) VALUES (1)", ()),
+        ])
+        .await
+        .expect_err("batch must fail");
+
+    match err {
+        BunnyDbError::Pipeline {
+            request_index,
+            code,
+            sql,
+            ..
+        } => {
+            assert_eq!(request_index, 1);
+            assert_eq!(code.as_deref(), Some("SQLITE_CONSTRAINT"));
+            assert_eq!(sql.as_deref(), Some("INSERT INTO users (id) VALUES (1)"));
+        }
+        _ => panic!("expected pipeline error"),
+    }
+}
+
+#[cfg(feature = "raw-mode")]
+#[tokio::test]
+async fn raw_pipeline_round_trips_custom_body() {
+    let response_body = json!({
+        "baton": null,
+        "results": [
+            { "type": "ok", "response": { "type": "store_sql" } },
+            { "type": "ok", "response": { "type": "close" } }
+        ]
+    });
+    let server = spawn_server(vec![MockResponse::json(
+        StatusCode::OK,
+        response_body.clone(),
+    )])
+    .await;
+    let db = BunnyDbClient::new_bearer(server.pipeline_url(), "token");
+
+    let request_body = json!({
+        "requests": [
+            { "type": "store_sql", "sql_id": 1, "sql": "SELECT 1" },
+            { "type": "close" }
+        ]
+    });
+    let response = db
+        .raw_pipeline(request_body.clone())
+        .await
+        .expect("raw pipeline must succeed");
+
+    assert_eq!(response.0, response_body);
+    assert_eq!(server.bodies(), vec![request_body]);
+    assert_eq!(server.authorizations(), vec!["Bearer token".to_owned()]);
+}
+
+#[cfg(feature = "raw-mode")]
+#[tokio::test]
+async fn query_raw_json_returns_untyped_rows() {
+    let server = spawn_server(vec![MockResponse::json(
+        StatusCode::OK,
+        query_pipeline_body(),
+    )])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let result = db
+        .query_raw_json("SELECT id, name FROM users", ())
+        .await
+        .expect("query must succeed");
+
+    assert_eq!(
+        result,
+        json!({
+            "cols": ["id", "name"],
+            "rows": [[1, "Kit"]],
+            "rows_read": null,
+            "rows_written": null,
+            "query_duration_ms": null
+        })
+    );
+}
+
+#[tokio::test]
+async fn prepared_statement_stores_sql_once_and_reuses_stream() {
+    let server = spawn_server(vec![
+        MockResponse::json(
+            StatusCode::OK,
+            json!({
+                "baton": "b1",
+                "results": [
+                    { "type": "ok", "response": { "type": "store_sql" } },
+                    exec_ok_result(1)
+                ]
+            }),
+        ),
+        MockResponse::json(
+            StatusCode::OK,
+            json!({ "baton": "b2", "results": [exec_ok_result(1)] }),
+        ),
+        MockResponse::json(
+            StatusCode::OK,
+            json!({
+                "results": [
+                    { "type": "ok", "response": { "type": "close_sql" } },
+                    { "type": "ok", "response": { "type": "close" } }
+                ]
+            }),
+        ),
+    ])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let mut insert = db.prepare("INSERT INTO users (name) VALUES (?)");
+    for name in ["Kit", "Lane"] {
+        let result = insert
+            .execute([Value::text(name)])
+            .await
+            .expect("prepared execute must succeed");
+        assert_eq!(result.affected_row_count, 1);
+    }
+    insert.close().await.expect("close must succeed");
+
+    let bodies = server.bodies();
+    assert_eq!(bodies.len(), 3);
+
+    let first = &bodies[0];
+    assert!(first.get("baton").is_none());
+    assert_eq!(first["requests"][0]["type"], "store_sql");
+    assert_eq!(
+        first["requests"][0]["sql"],
+        "INSERT INTO users (name) VALUES (?)"
+    );
+    let sql_id = first["requests"][0]["sql_id"].clone();
+    assert_eq!(first["requests"][1]["stmt"]["sql_id"], sql_id);
+    assert!(first["requests"][1]["stmt"].get("sql").is_none());
+
+    let second = &bodies[1];
+    assert_eq!(second["baton"], "b1");
+    assert_eq!(second["requests"].as_array().map(Vec::len), Some(1));
+    assert_eq!(second["requests"][0]["stmt"]["sql_id"], sql_id);
+    assert_eq!(second["requests"][0]["stmt"]["args"][0]["value"], "Lane");
+
+    let third = &bodies[2];
+    assert_eq!(third["baton"], "b2");
+    assert_eq!(third["requests"][0]["type"], "close_sql");
+    assert_eq!(third["requests"][0]["sql_id"], sql_id);
+    assert_eq!(third["requests"][1]["type"], "close");
+}
+
+#[tokio::test]
+async fn json_params_are_forwarded_as_named_args() {
+    let server = spawn_server(vec![MockResponse::json(
+        StatusCode::OK,
+        query_pipeline_body(),
+    )])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let params = Params::from_json(r#"{ "name": "Kit", "active": true, "score": 1.5 }"#)
+        .expect("params JSON must parse");
+    db.query(
+        "SELECT id, name FROM users WHERE name = :name AND active = :active AND score > :score",
+        params,
+    )
+    .await
+    .expect("query must succeed");
+
+    let body = &server.bodies()[0];
+    let stmt = &body["requests"][0]["stmt"];
+    assert!(stmt.get("args").is_none());
+    let mut named: Vec<(String, JsonValue)> = stmt["named_args"]
+        .as_array()
+        .expect("named args")
+        .iter()
+        .map(|arg| {
+            (
+                arg["name"].as_str().unwrap_or_default().to_owned(),
+                arg["value"].clone(),
+            )
+        })
+        .collect();
+    named.sort_by(|left, right| left.0.cmp(&right.0));
+    assert_eq!(
+        named,
+        [
+            (
+                "active".to_owned(),
+                json!({ "type": "integer", "value": "1" })
+            ),
+            ("name".to_owned(), json!({ "type": "text", "value": "Kit" })),
+            (
+                "score".to_owned(),
+                json!({ "type": "float", "value": "1.5" })
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn batch_items_mixes_prepared_and_adhoc_statements() {
+    let server = spawn_server(vec![MockResponse::json(
+        StatusCode::OK,
+        json!({
+            "results": [
+                { "type": "ok", "response": { "type": "store_sql" } },
+                exec_ok_result(1),
+                { "type": "error", "error": { "message": "no such table: logs" } },
+                exec_ok_result(1),
+                { "type": "ok", "response": { "type": "close" } }
+            ]
+        }),
+    )])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let insert = db.prepare("INSERT INTO users (name) VALUES (?)");
+    let outcomes = db
+        .batch_items([
+            BatchItem::Prepared(&insert, [Value::text("Kit")].into()),
+            BatchItem::Adhoc(Statement::execute("DELETE FROM logs", ())),
+            BatchItem::Prepared(&insert, [Value::text("Lane")].into()),
+        ])
+        .await
+        .expect("batch must succeed");
+
+    assert_eq!(outcomes.len(), 3);
+    assert!(
+        matches!(&outcomes[0], StatementOutcome::Exec(result) if result.affected_row_count == 1)
+    );
+    match &outcomes[1] {
+        StatementOutcome::SqlError {
+            request_index, sql, ..
+        } => {
+            assert_eq!(*request_index, 1);
+            assert_eq!(sql.as_deref(), Some("DELETE FROM logs"));
+        }
+        other => panic!("expected sql error, got {other:?}"),
+    }
+    assert!(matches!(&outcomes[2], StatementOutcome::Exec(_)));
+
+    let body = &server.bodies()[0];
+    let requests = body["requests"].as_array().expect("requests");
+    let types: Vec<_> = requests
+        .iter()
+        .map(|request| request["type"].as_str().unwrap_or_default())
+        .collect();
+    assert_eq!(
+        types,
+        ["store_sql", "execute", "execute", "execute", "close"]
+    );
+    let sql_id = requests[0]["sql_id"].clone();
+    assert_eq!(requests[1]["stmt"]["sql_id"], sql_id);
+    assert!(requests[1]["stmt"].get("sql").is_none());
+    assert_eq!(requests[2]["stmt"]["sql"], "DELETE FROM logs");
+    assert_eq!(requests[3]["stmt"]["sql_id"], sql_id);
+    assert_eq!(requests[3]["stmt"]["args"][0]["value"], "Lane");
+}
+
+#[test]
+fn prepared_statement_close_blocking_sends_close() {
+    let runtime = tokio::runtime::Runtime::new().expect("runtime must start");
+    let server = runtime.block_on(spawn_server(vec![
+        MockResponse::json(
+            StatusCode::OK,
+            json!({
+                "baton": "b1",
+                "results": [
+                    { "type": "ok", "response": { "type": "store_sql" } },
+                    exec_ok_result(1)
+                ]
+            }),
+        ),
+        MockResponse::json(
+            StatusCode::OK,
+            json!({
+                "results": [
+                    { "type": "ok", "response": { "type": "close_sql" } },
+                    { "type": "ok", "response": { "type": "close" } }
+                ]
+            }),
+        ),
+    ]));
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let mut delete = db.prepare("DELETE FROM users WHERE id = ?");
+    runtime
+        .block_on(delete.execute([Value::integer(1)]))
+        .expect("execute must succeed");
+    delete.close_blocking().expect("close must succeed");
+
+    let bodies = server.bodies();
+    assert_eq!(bodies.len(), 2);
+    assert_eq!(bodies[1]["baton"], "b1");
+    assert_eq!(bodies[1]["requests"][0]["type"], "close_sql");
+    assert_eq!(bodies[1]["requests"][1]["type"], "close");
+}
+
+#[test]
+fn client_close_blocking_closes_streams_dropped_outside_runtime() {
+    let runtime = tokio::runtime::Runtime::new().expect("runtime must start");
+    let server = runtime.block_on(spawn_server(vec![
+        MockResponse::json(
+            StatusCode::OK,
+            json!({
+                "baton": "b1",
+                "results": [
+                    { "type": "ok", "response": { "type": "store_sql" } },
+                    exec_ok_result(1)
+                ]
+            }),
+        ),
+        MockResponse::json(StatusCode::OK, stream_exec_body("t1")),
+        MockResponse::json(
+            StatusCode::OK,
+            json!({
+                "results": [
+                    { "type": "ok", "response": { "type": "close_sql" } },
+                    { "type": "ok", "response": { "type": "close" } }
+                ]
+            }),
+        ),
+        MockResponse::json(
+            StatusCode::OK,
+            json!({ "results": [{ "type": "ok", "response": { "type": "close" } }] }),
+        ),
+    ]));
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let mut delete = db.prepare("DELETE FROM users WHERE id = ?");
+    runtime
+        .block_on(delete.execute([Value::integer(1)]))
+        .expect("execute must succeed");
+    let tx = runtime.block_on(db.begin()).expect("begin must succeed");
+    drop(delete);
+    drop(tx);
+    assert_eq!(
+        server.bodies().len(),
+        2,
+        "drop outside a runtime sends nothing"
+    );
+
+    db.clone().close_blocking().expect("close must succeed");
+    db.close_blocking().expect("nothing is left to close");
+
+    let bodies = server.bodies();
+    assert_eq!(bodies.len(), 4);
+    assert_eq!(bodies[2]["baton"], "b1");
+    assert_eq!(bodies[2]["requests"][0]["type"], "close_sql");
+    assert_eq!(bodies[2]["requests"][1]["type"], "close");
+    assert_eq!(bodies[3]["baton"], "t1");
+    assert_eq!(bodies[3]["requests"], json!([{ "type": "close" }]));
+}
+
+#[tokio::test]
+async fn prepared_statement_restores_sql_after_failure_and_closes_on_drop() {
+    let server = spawn_server(vec![
+        MockResponse::json(
+            StatusCode::OK,
+            json!({
+                "baton": "b1",
+                "results": [
+                    { "type": "ok", "response": { "type": "store_sql" } },
+                    exec_ok_result(1)
+                ]
+            }),
+        ),
+        MockResponse::json(StatusCode::BAD_REQUEST, json!({"error": "stream expired"})),
+        MockResponse::json(
+            StatusCode::OK,
+            json!({
+                "baton": "b2",
+                "results": [
+                    { "type": "ok", "response": { "type": "store_sql" } },
+                    exec_ok_result(1)
+                ]
+            }),
+        ),
+        MockResponse::json(
+            StatusCode::OK,
+            json!({
+                "results": [
+                    { "type": "ok", "response": { "type": "close_sql" } },
+                    { "type": "ok", "response": { "type": "close" } }
+                ]
+            }),
+        ),
+    ])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let mut delete = db.prepare("DELETE FROM users WHERE id = ?");
+    delete
+        .execute([Value::integer(1)])
+        .await
+        .expect("first execute must succeed");
+    let err = delete
+        .execute([Value::integer(2)])
+        .await
+        .expect_err("expired stream must fail");
+    assert!(
+        matches!(err, BunnyDbError::SessionExpired { ref message } if message.contains("stream expired")),
+        "unexpected error: {err:?}"
+    );
+    delete
+        .execute([Value::integer(2)])
+        .await
+        .expect("execute must succeed on a new stream");
+    drop(delete);
+
+    for _ in 0..100 {
+        if server.hits.load(Ordering::SeqCst) == 4 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    let bodies = server.bodies();
+    assert_eq!(bodies.len(), 4);
+    assert_eq!(bodies[1]["baton"], "b1");
+    assert!(bodies[2].get("baton").is_none());
+    assert_eq!(bodies[2]["requests"][0]["type"], "store_sql");
+    assert_eq!(bodies[3]["baton"], "b2");
+    assert_eq!(bodies[3]["requests"][0]["type"], "close_sql");
+}
+
+#[tokio::test]
+async fn prepared_statement_close_ignores_expired_stream() {
+    let server = spawn_server(vec![
+        MockResponse::json(
+            StatusCode::OK,
+            json!({
+                "baton": "b1",
+                "results": [
+                    { "type": "ok", "response": { "type": "store_sql" } },
+                    exec_ok_result(1)
+                ]
+            }),
+        ),
+        MockResponse::json(
+            StatusCode::BAD_REQUEST,
+            json!({
+                "message": "The stream has expired due to inactivity",
+                "code": "STREAM_EXPIRED"
+            }),
+        ),
+    ])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let mut insert = db.prepare("INSERT INTO users (name) VALUES (?)");
+    insert
+        .execute([Value::text("Kit")])
+        .await
+        .expect("execute must succeed");
+    insert
+        .close()
+        .await
+        .expect("closing an expired stream is not an error");
+
+    let bodies = server.bodies();
+    assert_eq!(bodies.len(), 2);
+    assert_eq!(bodies[1]["baton"], "b1");
+    assert_eq!(bodies[1]["requests"][0]["type"], "close_sql");
+}
+
+/// Response to a single execute on a stream that stays open.
+fn stream_exec_body(baton: &str) -> JsonValue {
+    json!({
+        "baton": baton,
+        "results": [{
+            "type": "ok",
+            "response": { "type": "execute", "result": { "affected_row_count": 0 } }
+        }]
+    })
+}
+
+/// SQL text and baton of every single-statement request sent, with a
+/// trailing `+close` marker when the request also closed the stream.
+fn stream_calls(server: &TestServer) -> Vec<(String, Option<String>)> {
+    server
+        .bodies()
+        .iter()
+        .map(|body| {
+            let requests = body["requests"].as_array().expect("requests");
+            let mut sql = requests[0]["stmt"]["sql"].as_str().expect("sql").to_owned();
+            if requests.len() > 1 && requests[1]["type"] == "close" {
+                sql.push_str(" +close");
+            }
+            (sql, body["baton"].as_str().map(str::to_owned))
+        })
+        .collect()
+}
+
+fn call(sql: &str, baton: Option<&str>) -> (String, Option<String>) {
+    (sql.to_owned(), baton.map(str::to_owned))
+}
+
+#[tokio::test]
+async fn savepoint_release_is_kept_on_commit() {
+    let server = spawn_server(vec![
+        MockResponse::json(StatusCode::OK, stream_exec_body("b1")),
+        MockResponse::json(StatusCode::OK, stream_exec_body("b2")),
+        MockResponse::json(StatusCode::OK, stream_exec_body("b3")),
+        MockResponse::json(StatusCode::OK, stream_exec_body("b4")),
+        MockResponse::json(StatusCode::OK, execute_pipeline_body(0, None)),
+    ])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let mut tx = db.begin().await.expect("begin must succeed");
+    let mut step = tx.savepoint("step").await.expect("savepoint must start");
+    step.execute("INSERT INTO users (name) VALUES ('Kit')", ())
+        .await
+        .expect("insert must succeed");
+    step.release().await.expect("release must succeed");
+    tx.commit().await.expect("commit must succeed");
+
+    assert_eq!(
+        stream_calls(&server),
+        [
+            call("BEGIN", None),
+            call("SAVEPOINT \"step\"", Some("b1")),
+            call("INSERT INTO users (name) VALUES ('Kit')", Some("b2")),
+            call("RELEASE \"step\"", Some("b3")),
+            call("COMMIT +close", Some("b4")),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn savepoint_rollback_keeps_outer_transaction() {
+    let failed = json!({
+        "baton": "b4",
+        "results": [{
+            "type": "error",
+            "error": { "message": "UNIQUE constraint failed: users.name", "code": "SQLITE_CONSTRAINT" }
+        }]
+    });
+    let server = spawn_server(vec![
+        MockResponse::json(StatusCode::OK, stream_exec_body("b1")),
+        MockResponse::json(StatusCode::OK, stream_exec_body("b2")),
+        MockResponse::json(StatusCode::OK, stream_exec_body("b3")),
+        MockResponse::json(StatusCode::OK, failed),
+        MockResponse::json(StatusCode::OK, stream_exec_body("b5")),
+        MockResponse::json(StatusCode::OK, stream_exec_body("b6")),
+        MockResponse::json(StatusCode::OK, stream_exec_body("b7")),
+        MockResponse::json(StatusCode::OK, execute_pipeline_body(0, None)),
+    ])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let mut tx = db.begin().await.expect("begin must succeed");
+    tx.execute("INSERT INTO orders (id) VALUES (1)", ())
+        .await
+        .expect("outer insert must succeed");
+    let mut line = tx.savepoint("line").await.expect("savepoint must start");
+    let err = line
+        .execute("INSERT INTO users (name) VALUES ('Kit')", ())
+        .await
+        .expect_err("duplicate must fail");
+    assert!(matches!(err, BunnyDbError::Pipeline { .. }));
+    line.rollback()
+        .await
+        .expect("rollback to savepoint must succeed");
+    tx.execute("INSERT INTO orders (id) VALUES (2)", ())
+        .await
+        .expect("outer transaction must stay usable");
+    tx.commit().await.expect("commit must succeed");
+
+    assert_eq!(
+        stream_calls(&server),
+        [
+            call("BEGIN", None),
+            call("INSERT INTO orders (id) VALUES (1)", Some("b1")),
+            call("SAVEPOINT \"line\"", Some("b2")),
+            call("INSERT INTO users (name) VALUES ('Kit')", Some("b3")),
+            call("ROLLBACK TO \"line\"", Some("b4")),
+            call("RELEASE \"line\"", Some("b5")),
+            call("INSERT INTO orders (id) VALUES (2)", Some("b6")),
+            call("COMMIT +close", Some("b7")),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn dropped_savepoint_is_rolled_back_before_next_statement() {
+    let server = spawn_server(vec![
+        MockResponse::json(StatusCode::OK, stream_exec_body("b1")),
+        MockResponse::json(StatusCode::OK, stream_exec_body("b2")),
+        MockResponse::json(StatusCode::OK, stream_exec_body("b3")),
+        MockResponse::json(StatusCode::OK, stream_exec_body("b4")),
+        MockResponse::json(StatusCode::OK, execute_pipeline_body(0, None)),
+    ])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let mut tx = db.begin().await.expect("begin must succeed");
+    let err = tx
+        .savepoint("bad name")
+        .await
+        .expect_err("invalid savepoint name must be rejected");
+    assert!(matches!(err, BunnyDbError::Decode(_)));
+    drop(tx.savepoint("scratch").await.expect("savepoint must start"));
+    tx.commit().await.expect("commit must succeed");
+
+    assert_eq!(
+        stream_calls(&server),
+        [
+            call("BEGIN", None),
+            call("SAVEPOINT \"scratch\"", Some("b1")),
+            call("ROLLBACK TO \"scratch\"", Some("b2")),
+            call("RELEASE \"scratch\"", Some("b3")),
+            call("COMMIT +close", Some("b4")),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn describe_returns_columns_without_executing() {
+    let server = spawn_server(vec![MockResponse::json(
+        StatusCode::OK,
+        json!({
+            "results": [
+                {
+                    "type": "ok",
+                    "response": {
+                        "type": "describe",
+                        "result": {
+                            "params": [{ "name": null }],
+                            "cols": [
+                                { "name": "id", "decltype": "INTEGER" },
+                                { "name": "name", "decltype": null }
+                            ],
+                            "is_explain": false,
+                            "is_readonly": true
+                        }
+                    }
+                },
+                { "type": "ok", "response": { "type": "close" } }
+            ]
+        }),
+    )])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let cols = db
+        .describe("SELECT id, name FROM users WHERE id = ?")
+        .await
+        .expect("describe must succeed");
+
+    assert_eq!(cols.len(), 2);
+    assert_eq!(cols[0].name, "id");
+    assert_eq!(cols[0].decltype.as_deref(), Some("INTEGER"));
+    assert_eq!(cols[1].name, "name");
+    assert_eq!(cols[1].decltype, None);
+
+    let body = &server.bodies()[0];
+    assert_eq!(body["requests"][0]["type"], "describe");
+    assert_eq!(
+        body["requests"][0]["sql"],
+        "SELECT id, name FROM users WHERE id = ?"
+    );
+    assert_eq!(body["requests"][1]["type"], "close");
+}
+
+#[tokio::test]
+async fn batch_lenient_reports_undecodable_statement() {
+    let bad_query = json!({
+        "type": "ok",
+        "response": {
+            "type": "execute",
+            "result": {
+                "cols": [{ "name": "n" }],
+                "rows": [[{ "type": "integer", "value": "not-a-number" }]]
+            }
+        }
+    });
+    let good_query = json!({
+        "type": "ok",
+        "response": {
+            "type": "execute",
+            "result": {
+                "cols": [{ "name": "n" }],
+                "rows": [[{ "type": "integer", "value": "2" }]]
+            }
+        }
+    });
+    let body = json!({
+        "results": [
+            exec_ok_result(1),
+            bad_query,
+            good_query,
+            { "type": "ok", "response": { "type": "close" } }
+        ]
+    });
+    let server = spawn_server(vec![
+        MockResponse::json(StatusCode::OK, body.clone()),
+        MockResponse::json(StatusCode::OK, body),
+    ])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+    let statements = || {
+        [
+            Statement::execute("INSERT INTO t VALUES (1)", ()),
+            Statement::query("SELECT broken AS n", ()),
+            Statement::query("SELECT 2 AS n", ()),
+        ]
+    };
+
+    let err = db
+        .batch(statements())
+        .await
+        .expect_err("plain batch must fail on the bad value");
+    assert!(matches!(err, BunnyDbError::Decode(_)));
+
+    let outcomes = db
+        .batch_lenient(statements())
+        .await
+        .expect("lenient batch must succeed");
+
+    assert_eq!(outcomes.len(), 3);
+    assert_eq!(
+        outcomes[0].as_exec().map(|exec| exec.affected_row_count),
+        Some(1)
+    );
+    match &outcomes[1] {
+        StatementOutcome::DecodeError {
+            request_index,
+            message,
+        } => {
+            assert_eq!(*request_index, 1);
+            assert!(message.contains("not-a-number"));
+        }
+        other => panic!("expected decode error, got {other:?}"),
+    }
+    assert!(outcomes[1].is_error());
+    assert_eq!(
+        outcomes[2].as_query().map(|query| query.rows[0][0].clone()),
+        Some(Value::Integer(2))
+    );
+}
+
+#[tokio::test]
+async fn request_inspector_sees_payload_on_every_attempt() {
+    let server = spawn_server(vec![
+        MockResponse::json(StatusCode::SERVICE_UNAVAILABLE, json!({"error": "busy"})),
+        MockResponse::json(StatusCode::OK, execute_pipeline_body(1, None)),
+    ])
+    .await;
+
+    let payloads = Arc::new(Mutex::new(Vec::<String>::new()));
+    let sink = payloads.clone();
+    let db = BunnyDbClient::new_bearer(server.pipeline_url(), "super-secret-token")
+        .with_options(ClientOptions {
+            max_retries: 1,
+            retry_backoff_ms: 1,
+            compression: true,
+            ..ClientOptions::default()
+        })
+        .with_request_inspector(Arc::new(move |json: &str| {
+            sink.lock()
+                .expect("payload mutex must not be poisoned")
+                .push(json.to_owned());
+        }));
+
+    db.execute("DELETE FROM users WHERE id = ?", [Value::integer(7)])
+        .await
+        .expect("request must succeed after retry");
+
+    let payloads = payloads
+        .lock()
+        .expect("payload mutex must not be poisoned")
+        .clone();
+    assert_eq!(payloads.len(), 2);
+    assert_eq!(payloads[0], payloads[1]);
+    let parsed: JsonValue = serde_json::from_str(&payloads[0]).expect("payload must be JSON");
+    assert_eq!(parsed, server.bodies()[0]);
+    assert!(payloads[0].contains("DELETE FROM users WHERE id = ?"));
+    assert!(!payloads[0].contains("super-secret-token"));
+}
+
+#[tokio::test]
+async fn empty_batch_sends_no_request() {
+    let server = spawn_server(Vec::new()).await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    let outcomes = db
+        .batch(Vec::<Statement>::new())
+        .await
+        .expect("empty batch must succeed");
+    assert!(outcomes.is_empty());
+
+    let outcomes = db
+        .batch_strict(Vec::<Statement>::new())
+        .await
+        .expect("empty strict batch must succeed");
+    assert!(outcomes.is_empty());
+
+    assert_eq!(server.hits.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn empty_success_body_reports_clear_error() {
+    let server = spawn_server(vec![
+        MockResponse::text(StatusCode::OK, "application/json", ""),
+        MockResponse::text(StatusCode::NO_CONTENT, "application/json", ""),
+    ])
+    .await;
+    let db = BunnyDbClient::new(server.pipeline_url(), "token");
+
+    for status in [200, 204] {
+        let err = db
+            .execute("DELETE FROM users", ())
+            .await
+            .expect_err("empty body must fail");
+        match err {
+            BunnyDbError::Decode(message) => {
+                assert_eq!(message, format!("empty response body (status {status})"));
+            }
+            other => panic!("expected decode error, got {other:?}"),
+        }
     }
 }
@@ -3,7 +3,10 @@ use std::{
     time::{SystemTime, UNIX_EPOCH},
 };
 
-use bunnydb_http::{BunnyDbClient, BunnyDbError, Params, Statement, StatementOutcome, Value};
+use bunnydb_http::{
+    normalize_pipeline_url, quote_identifier, BunnyDbClient, BunnyDbError, Params, Statement,
+    StatementOutcome, Value,
+};
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
@@ -18,31 +21,12 @@ struct SecretsFile {
     bunny_database_auth_token: Option<String>,
 }
 
-fn to_pipeline_url(url: &str) -> String {
-    let trimmed = url.trim_end_matches('/');
-    if trimmed.ends_with("/v2/pipeline") {
-        return trimmed.to_owned();
-    }
-    if let Some(host) = trimmed.strip_prefix("libsql://") {
-        return format!("https://{host}/v2/pipeline");
-    }
-    format!("{trimmed}/v2/pipeline")
-}
-
-fn to_authorization_token(token: String) -> String {
-    if token.contains(' ') {
-        token
-    } else {
-        format!("Bearer {token}")
-    }
-}
-
 fn load_live_credentials() -> Result<(String, String), String> {
     if let (Ok(pipeline_url), Ok(token)) = (
         std::env::var("BUNNYDB_PIPELINE_URL"),
         std::env::var("BUNNYDB_TOKEN"),
     ) {
-        return Ok((pipeline_url, to_authorization_token(token)));
+        return Ok((pipeline_url, token));
     }
 
     let content = fs::read_to_string("secrets.json").map_err(|_| {
@@ -53,7 +37,11 @@ fn load_live_credentials() -> Result<(String, String), String> {
 
     let pipeline_url = parsed
         .bunnydb_pipeline_url
-        .or_else(|| parsed.bunny_database_url.map(|url| to_pipeline_url(&url)))
+        .or_else(|| {
+            parsed
+                .bunny_database_url
+                .map(|url| normalize_pipeline_url(&url))
+        })
         .ok_or_else(|| {
             "missing BUNNYDB_PIPELINE_URL or BUNNY_DATABASE_URL in secrets.json".to_owned()
         })?;
@@ -64,7 +52,7 @@ fn load_live_credentials() -> Result<(String, String), String> {
             "missing BUNNYDB_TOKEN or BUNNY_DATABASE_AUTH_TOKEN in secrets.json".to_owned()
         })?;
 
-    Ok((pipeline_url, to_authorization_token(token)))
+    Ok((pipeline_url, token))
 }
 
 fn unique_suffix() -> u128 {
@@ -84,8 +72,9 @@ async fn live_roundtrip_and_batch_error_propagation() {
         }
     };
 
-    let db = BunnyDbClient::new(pipeline_url, token);
-    let table = format!("users_live_{}", unique_suffix());
+    let db = BunnyDbClient::new_auto_auth(pipeline_url, token);
+    let table = quote_identifier(&format!("users_live_{}", unique_suffix()))
+        .expect("generated table name must be a valid identifier");
 
     db.execute(
         &format!("CREATE TABLE IF NOT EXISTS {table} (id INTEGER PRIMARY KEY, name TEXT NOT NULL)"),
@@ -136,6 +125,13 @@ async fn live_roundtrip_and_batch_error_propagation() {
     ));
     assert!(matches!(outcomes[2], StatementOutcome::Query(_)));
 
+    let floats = [0.3, 0.1 + 0.2, 1e300, -1.25e-300];
+    let echoed = db
+        .query("SELECT ?, ?, ?, ?", floats.map(Value::float))
+        .await
+        .expect("float echo must succeed");
+    assert_eq!(echoed.rows[0], floats.map(Value::float));
+
     let cleanup_result = db
         .execute(&format!("DROP TABLE IF EXISTS {table}"), ())
         .await;